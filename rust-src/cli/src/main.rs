@@ -0,0 +1,499 @@
+//! Command line interface for Character Builder: validates Assets and
+//! Characters, and builds and exports Character Sheets.
+//!
+//! Exits with 0 when everything is fine, 1 when something is invalid, and 2
+//! when the command itself failed, such as an unreadable `--data` directory.
+//! `validate` and `lint` print their diagnostics with `--output json` as a
+//! list of objects, each with the `file` it is in, the `error` and its fields,
+//! and a `message`.
+#![warn(missing_docs)]
+use std::fmt;
+use std::fs;
+use std::io::{self, Cursor, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::builder::PossibleValuesParser;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde_json::Value;
+use validator::assets::character::Character;
+use validator::assets::Asset;
+use validator::character_sheet::CharacterSheet;
+use validator::library::{self, Library};
+use validator::schema;
+use validator::validation::Validate;
+use validator::ValidatorError;
+
+/// Exit code when something is invalid
+const INVALID: u8 = 1;
+/// Exit code when the command failed
+const FAILED: u8 = 2;
+
+/// Validates and builds Characters for Character Builder
+#[derive(Debug, Parser)]
+#[command(name = "validator", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Checks that Assets parse, and when `--data` is given, that Characters
+    /// follow the rules and other Assets only refer to Assets that exist
+    Validate {
+        /// Asset files, or directories of them
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// Asset files, or directories of them, that Assets are validated against
+        #[arg(long)]
+        data: Vec<PathBuf>,
+        /// How diagnostics are printed
+        #[arg(long, value_enum, default_value_t = Output::Text)]
+        output: Output,
+    },
+    /// Prints the Character Sheet of a Character as JSON
+    Sheet {
+        /// The Character
+        character: PathBuf,
+        /// Asset files, or directories of them, that the Character is built from
+        #[arg(long, required = true)]
+        data: Vec<PathBuf>,
+    },
+    /// Writes the Character Sheet of a Character in another format
+    Export {
+        /// The Character
+        character: PathBuf,
+        /// Asset files, or directories of them, that the Character is built from
+        #[arg(long, required = true)]
+        data: Vec<PathBuf>,
+        /// Format to write
+        #[arg(long, value_enum)]
+        format: Format,
+        /// File to write to, instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// PDF with a form to fill in, for `--format pdf`
+        #[arg(long)]
+        template: Option<PathBuf>,
+    },
+    /// Checks that every Asset in a directory parses, that no two have the same
+    /// id, that every Character in it follows the rules with the other Assets in
+    /// it, and that the other Assets only refer to Assets in it
+    Lint {
+        /// The directory
+        data: PathBuf,
+        /// How diagnostics are printed
+        #[arg(long, value_enum, default_value_t = Output::Text)]
+        output: Output,
+    },
+    /// Prints a JSON Schema
+    Schema {
+        /// What the schema is of
+        #[arg(default_value = "asset", value_parser = schema_names())]
+        name: String,
+    },
+}
+
+/// How diagnostics are printed
+#[derive(Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+enum Output {
+    /// A line for each, with its file
+    Text,
+    /// A JSON list
+    Json,
+}
+
+/// A format a sheet can be exported in
+#[derive(Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+enum Format {
+    /// A Foundry VTT actor
+    Foundry,
+    /// A MapTool token
+    Rptok,
+    /// A PDF
+    Pdf,
+    /// Markdown
+    Markdown,
+    /// A standalone HTML page
+    Html,
+}
+
+impl Format {
+    /// Returns whether the format is binary, rather than text
+    fn is_binary(&self) -> bool {
+        matches!(self, Format::Rptok | Format::Pdf)
+    }
+}
+
+/// Error for running a command
+#[derive(Debug)]
+enum Error {
+    /// Assets or the Character couldn't be loaded
+    Validator(ValidatorError),
+    /// The sheet couldn't be exported
+    Export(String),
+    /// The output couldn't be serialized
+    Serialize(serde_json::Error),
+    /// The output couldn't be written
+    Write(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Validator(e) => write!(f, "{}", e),
+            Self::Export(message) => write!(f, "could not export sheet: {}", message),
+            Self::Serialize(e) => write!(f, "could not serialize output: {}", e),
+            Self::Write(e) => write!(f, "could not write output: {}", e),
+        }
+    }
+}
+
+impl ::std::error::Error for Error {}
+
+impl From<ValidatorError> for Error {
+    fn from(e: ValidatorError) -> Self {
+        Self::Validator(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Serialize(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::Write(e)
+    }
+}
+
+/// Something wrong in a file
+#[derive(Debug, PartialEq)]
+struct Diagnostic {
+    file: PathBuf,
+    error: ValidatorError,
+}
+
+impl Diagnostic {
+    /// Returns the diagnostic as an object, see [`ValidatorError::diagnostic`]
+    fn to_json(&self) -> Value {
+        let mut diagnostic = self.error.diagnostic();
+        if let Value::Object(fields) = &mut diagnostic {
+            fields.insert(String::from("file"), self.file.display().to_string().into());
+        }
+        diagnostic
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.error {
+            // These already say which file they are in
+            ValidatorError::Read { .. } | ValidatorError::Parse { path: Some(_), .. } => {
+                write!(f, "{}", self.error)
+            }
+            error => write!(f, "{}: {}", self.file.display(), error),
+        }
+    }
+}
+
+fn schema_names() -> PossibleValuesParser {
+    PossibleValuesParser::new(schema::schemas().into_iter().map(|(name, _)| name))
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(FAILED)
+        }
+    }
+}
+
+fn run(command: Command) -> Result<ExitCode, Error> {
+    match command {
+        Command::Validate {
+            files,
+            data,
+            output,
+        } => {
+            let library = match data.is_empty() {
+                true => None,
+                false => Some(load(&data)?),
+            };
+            let (assets, mut diagnostics) = parse(&files);
+            if let Some(library) = &library {
+                diagnostics.extend(check(&assets, library));
+            }
+
+            report(&diagnostics, output)
+        }
+        Command::Sheet { character, data } => {
+            let sheet = build(&character, &data)?;
+
+            let json = serde_json::to_string_pretty(&sheet)?;
+            writeln!(io::stdout(), "{}", json)?;
+
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Export {
+            character,
+            data,
+            format,
+            output,
+            template,
+        } => {
+            if template.is_some() && format != Format::Pdf {
+                return Err(Error::Export(String::from(
+                    "a template is only used with `--format pdf`",
+                )));
+            }
+            let bytes = export(&build(&character, &data)?, format, template.as_deref())?;
+
+            match output {
+                Some(path) => fs::write(path, bytes)?,
+                None if format.is_binary() && io::stdout().is_terminal() => {
+                    return Err(Error::Export(format!(
+                        "{:?} is binary, write it to a file with `--output`",
+                        format
+                    )));
+                }
+                None => io::stdout().write_all(&bytes)?,
+            }
+
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Lint { data, output } => {
+            let (assets, mut diagnostics) = parse(&[data]);
+            let mut library = Library::new();
+            for (file, asset) in &assets {
+                if matches!(asset, Asset::Character(_)) {
+                    continue;
+                }
+                if library.insert(asset.clone()).is_some() {
+                    diagnostics.push(Diagnostic {
+                        file: file.clone(),
+                        error: ValidatorError::DuplicateAsset {
+                            kind: String::from(asset.kind()),
+                            id: asset.metadata().id.clone(),
+                        },
+                    });
+                }
+            }
+            diagnostics.extend(check(&assets, &library));
+
+            report(&diagnostics, output)
+        }
+        Command::Schema { name } => {
+            let schemas = schema::schemas();
+            // The name is one of the schemas, as clap checked it
+            if let Some((_, schema)) = schemas.iter().find(|(n, _)| *n == name) {
+                writeln!(io::stdout(), "{}", schema::to_string(schema)?)?;
+            }
+
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+/// Loads every Asset at each of `paths` into one Library
+fn load(paths: &[PathBuf]) -> Result<Library, ValidatorError> {
+    let mut library = Library::new();
+    for path in paths {
+        library.add_path(path)?;
+    }
+
+    Ok(library)
+}
+
+/// Parses every yaml file at each of `paths`, returning each Asset, in the order
+/// they are written, with the file it is in, and a diagnostic for each file that
+/// couldn't be read or parsed
+fn parse(paths: &[PathBuf]) -> (Vec<(PathBuf, Asset)>, Vec<Diagnostic>) {
+    let mut assets = vec![];
+    let mut diagnostics = vec![];
+
+    for path in paths {
+        let files = match library::files(path) {
+            Ok(files) => files,
+            Err(error) => {
+                diagnostics.push(Diagnostic {
+                    file: path.clone(),
+                    error,
+                });
+                continue;
+            }
+        };
+
+        for file in files {
+            match library::read_assets(&file) {
+                Ok(read) => assets.extend(read.into_iter().map(|asset| (file.clone(), asset))),
+                Err(error) => diagnostics.push(Diagnostic { file, error }),
+            }
+        }
+    }
+
+    (assets, diagnostics)
+}
+
+/// Validates every Asset in `assets` against `library`
+fn check(assets: &[(PathBuf, Asset)], library: &Library) -> Vec<Diagnostic> {
+    assets
+        .iter()
+        .flat_map(|(file, asset)| diagnose(file, asset, library))
+        .collect()
+}
+
+/// Validates `asset`, from `file`, against `library`
+fn diagnose(file: &Path, asset: &Asset, library: &Library) -> Vec<Diagnostic> {
+    match asset.validate(library) {
+        Ok(()) => vec![],
+        Err(errors) => errors
+            .into_iter()
+            .map(|error| Diagnostic {
+                file: file.to_path_buf(),
+                error,
+            })
+            .collect(),
+    }
+}
+
+/// Prints `diagnostics`, returning the exit code for them
+fn report(diagnostics: &[Diagnostic], output: Output) -> Result<ExitCode, Error> {
+    let mut stdout = io::stdout().lock();
+    match output {
+        Output::Text => {
+            for diagnostic in diagnostics {
+                writeln!(stdout, "{}", diagnostic)?;
+            }
+        }
+        Output::Json => {
+            let diagnostics: Vec<_> = diagnostics.iter().map(Diagnostic::to_json).collect();
+            writeln!(stdout, "{}", Value::from(diagnostics))?;
+        }
+    }
+
+    Ok(match diagnostics.is_empty() {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::from(INVALID),
+    })
+}
+
+/// Reads the Character at `path`
+fn character(path: &Path) -> Result<Character, ValidatorError> {
+    match validator::read_asset(path)? {
+        Asset::Character(character) => Ok(character),
+        _ => Err(ValidatorError::Parse {
+            path: Some(path.display().to_string()),
+            message: String::from("the asset is not a character"),
+        }),
+    }
+}
+
+/// Builds the sheet of the Character at `path` from the Assets at `data`
+fn build(path: &Path, data: &[PathBuf]) -> Result<CharacterSheet, ValidatorError> {
+    Ok(CharacterSheet::build(&character(path)?, &load(data)?))
+}
+
+/// Returns `sheet` in `format`
+fn export(
+    sheet: &CharacterSheet,
+    format: Format,
+    template: Option<&Path>,
+) -> Result<Vec<u8>, Error> {
+    let export_error = |e: &dyn fmt::Display| Error::Export(e.to_string());
+
+    match format {
+        Format::Foundry => Ok((foundry::to_string(sheet) + "\n").into_bytes()),
+        Format::Rptok => rptok::Token::from(sheet)
+            .write(Cursor::new(vec![]))
+            .map(Cursor::into_inner)
+            .map_err(|e| export_error(&e)),
+        Format::Pdf => {
+            let mut bytes = vec![];
+            pdf_sheet::write(sheet, template, &mut bytes).map_err(|e| export_error(&e))?;
+            Ok(bytes)
+        }
+        Format::Markdown => render::markdown(sheet)
+            .map(String::into_bytes)
+            .map_err(|e| export_error(&e)),
+        Format::Html => render::html(sheet)
+            .map(String::into_bytes)
+            .map_err(|e| export_error(&e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_cli_parse() {
+        let cli = Cli::try_parse_from([
+            "validator",
+            "export",
+            "character.yml",
+            "--data",
+            "class",
+            "--data",
+            "trait",
+            "--format",
+            "rptok",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Command::Export {
+                data,
+                format,
+                output,
+                ..
+            } => {
+                assert_eq!(vec![PathBuf::from("class"), PathBuf::from("trait")], data);
+                assert_eq!(Format::Rptok, format);
+                assert!(output.is_none());
+            }
+            command => panic!("{:?} is not export", command),
+        }
+
+        assert!(Cli::try_parse_from(["validator", "validate"]).is_err());
+        assert!(Cli::try_parse_from(["validator", "sheet", "character.yml"]).is_err());
+        assert!(Cli::try_parse_from(["validator", "schema", "spell"]).is_err());
+    }
+
+    #[test]
+    fn test_diagnostic_to_json() {
+        let diagnostic = Diagnostic {
+            file: PathBuf::from("batman.yml"),
+            error: ValidatorError::UnknownAsset {
+                kind: String::from("feat"),
+                id: String::from("alert"),
+            },
+        };
+
+        assert_eq!(
+            serde_json::json!({
+                "file": "batman.yml",
+                "error": "unknown-asset",
+                "kind": "feat",
+                "id": "alert",
+                "message": "no feat with the id `alert`",
+            }),
+            diagnostic.to_json()
+        );
+        assert_eq!(
+            "batman.yml: no feat with the id `alert`",
+            diagnostic.to_string()
+        );
+    }
+}
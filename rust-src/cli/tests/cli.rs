@@ -0,0 +1,200 @@
+//! Runs the `validator` binary on the Characters in `tests/characters`, with
+//! the Assets in `data`, and on the broken Assets in `tests/assets` and
+//! `tests/duplicates`.
+use std::fs;
+use std::process::{Command, Output};
+
+use serde_json::Value;
+
+/// Asset files and directories the Characters are built from
+const DATA: [&str; 8] = [
+    "class/fighter.yml",
+    "background",
+    "feature",
+    "item",
+    "language.yml",
+    "size.yml",
+    "trait",
+    "vision.yml",
+];
+
+fn validator(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_validator"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Runs a command that takes `--data`, with every path in [`DATA`]
+fn with_data(args: &[&str]) -> Output {
+    let data: Vec<String> = DATA
+        .iter()
+        .flat_map(|path| [String::from("--data"), format!("../../data/{}", path)])
+        .collect();
+
+    Command::new(env!("CARGO_BIN_EXE_validator"))
+        .args(args)
+        .args(data)
+        .output()
+        .unwrap()
+}
+
+fn json(output: &Output) -> Value {
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_validate() {
+    let output = with_data(&["validate", "tests/characters/acolyte.yml"]);
+
+    assert_eq!(Some(0), output.status.code());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_validate_invalid() {
+    let output = with_data(&["validate", "tests/characters", "--output", "json"]);
+
+    assert_eq!(Some(1), output.status.code());
+    let diagnostics = json(&output);
+    assert_eq!(
+        serde_json::json!({
+            "file": "tests/characters/veteran.yml",
+            "error": "level-exceeds-experience",
+            "level": 5,
+            "experience": 0,
+            "message": "level 5 needs more than 0 experience points (allows level 1)",
+        }),
+        diagnostics[0]
+    );
+    assert!(diagnostics
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|d| d["file"] == "tests/characters/veteran.yml"));
+}
+
+#[test]
+fn test_validate_without_data() {
+    // Without `--data` Characters are only parsed
+    let output = validator(&["validate", "tests/characters", "tests/assets"]);
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!(
+        "could not parse tests/assets/bard.yml: data did not match any variant of untagged enum Spec\n",
+        String::from_utf8(output.stdout).unwrap()
+    );
+
+    let output = validator(&["validate", "tests/characters"]);
+    assert_eq!(Some(0), output.status.code());
+}
+
+#[test]
+fn test_lint() {
+    let output = validator(&["lint", "tests/assets", "--output", "json"]);
+
+    assert_eq!(Some(1), output.status.code());
+    let diagnostics = json(&output);
+    assert_eq!("parse", diagnostics[0]["error"]);
+    assert_eq!("tests/assets/bard.yml", diagnostics[0]["file"]);
+    // The Character is checked against the other Assets in the directory
+    assert!(diagnostics.as_array().unwrap()[1..]
+        .iter()
+        .all(|d| { d["file"] == "tests/assets/stray.yml" && d["error"] == "unknown-asset" }));
+}
+
+#[test]
+fn test_lint_duplicates() {
+    let output = validator(&["lint", "tests/duplicates"]);
+
+    assert_eq!(Some(1), output.status.code());
+    // Every Asset is checked on its own, in the order it is written
+    assert_eq!(
+        "tests/duplicates/b.yml: another background has the id `dup`
+tests/duplicates/b.yml: another background has the id `dup`
+tests/duplicates/a.yml: no language with the id `nope-a`
+tests/duplicates/b.yml: no language with the id `nope-b`
+tests/duplicates/b.yml: no language with the id `nope-c`
+",
+        String::from_utf8(output.stdout).unwrap()
+    );
+}
+
+#[test]
+fn test_lint_data() {
+    let output = validator(&["lint", "../../data"]);
+
+    assert_eq!(Some(0), output.status.code());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_sheet() {
+    let output = with_data(&["sheet", "tests/characters/acolyte.yml"]);
+
+    assert_eq!(Some(0), output.status.code());
+    let sheet = json(&output);
+    assert_eq!("Brother Tam", sheet["name"]);
+    assert_eq!(2, sheet["proficiency_bonus"]);
+}
+
+#[test]
+fn test_sheet_missing_data() {
+    let output = validator(&[
+        "sheet",
+        "tests/characters/acolyte.yml",
+        "--data",
+        "tests/missing",
+    ]);
+
+    assert_eq!(Some(2), output.status.code());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("error: could not read tests/missing"));
+}
+
+#[test]
+fn test_export() {
+    let output = with_data(&[
+        "export",
+        "tests/characters/acolyte.yml",
+        "--format",
+        "markdown",
+    ]);
+    assert_eq!(Some(0), output.status.code());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("# Brother Tam\n"));
+
+    let output = with_data(&[
+        "export",
+        "tests/characters/acolyte.yml",
+        "--format",
+        "foundry",
+    ]);
+    assert_eq!("Brother Tam", json(&output)["name"]);
+
+    let path = ::std::env::temp_dir().join(format!("cli-export-{}.rptok", ::std::process::id()));
+    let output = with_data(&[
+        "export",
+        "tests/characters/acolyte.yml",
+        "--format",
+        "rptok",
+        "--output",
+        path.to_str().unwrap(),
+    ]);
+    assert_eq!(Some(0), output.status.code());
+    let token = fs::read(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    // An rptok file is a zip
+    assert!(token.starts_with(b"PK"));
+}
+
+#[test]
+fn test_schema() {
+    let output = validator(&["schema"]);
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("Asset", json(&output)["title"]);
+
+    let output = validator(&["schema", "character"]);
+    assert_eq!("Character", json(&output)["title"]);
+}
@@ -0,0 +1,254 @@
+//! Converts a Character Sheet to a Foundry VTT actor, for the dnd5e system.
+//!
+//! The actor is a JSON document that can be imported in Foundry with
+//! "Import Data" on an actor's context menu.
+#![warn(missing_docs)]
+use serde_json::{json, Map, Value};
+use validator::assets::character::{Ability, Skill};
+use validator::assets::item::ItemCategory;
+use validator::assets::Movement;
+use validator::character_sheet::{Attack, CharacterSheet};
+
+/// Senses the dnd5e system has a field for, anything else is a special sense
+const SENSES: [&str; 4] = ["blindsight", "darkvision", "tremorsense", "truesight"];
+
+/// Returns the dnd5e key of `ability`, such as `str`
+fn ability_key(ability: Ability) -> &'static str {
+    &ability.name()[..3]
+}
+
+/// Returns the dnd5e key of `skill`, such as `ath`
+fn skill_key(skill: Skill) -> &'static str {
+    match skill {
+        Skill::Acrobatics => "acr",
+        Skill::AnimalHandling => "ani",
+        Skill::Arcana => "arc",
+        Skill::Athletics => "ath",
+        Skill::Deception => "dec",
+        Skill::History => "his",
+        Skill::Insight => "ins",
+        Skill::Intimidation => "itm",
+        Skill::Investigation => "inv",
+        Skill::Medicine => "med",
+        Skill::Nature => "nat",
+        Skill::Perception => "prc",
+        Skill::Performance => "prf",
+        Skill::Persuasion => "per",
+        Skill::Religion => "rel",
+        Skill::SleightOfHand => "slt",
+        Skill::Stealth => "ste",
+        Skill::Survival => "sur",
+    }
+}
+
+/// Returns the dnd5e key of the size `id`, such as `med`
+fn size_key(id: &str) -> &str {
+    match id {
+        "small" => "sm",
+        "medium" => "med",
+        "large" => "lg",
+        "gargantuan" => "grg",
+        _ => id,
+    }
+}
+
+/// Returns the dnd5e key of `movement`
+fn movement_key(movement: Movement) -> &'static str {
+    match movement {
+        Movement::Walk => "walk",
+        Movement::Fly => "fly",
+        Movement::Climb => "climb",
+        Movement::Swim => "swim",
+        Movement::Burrow => "burrow",
+    }
+}
+
+/// Returns the dnd5e item type of an Item in `category`
+fn item_type(category: ItemCategory) -> &'static str {
+    match category {
+        ItemCategory::Weapon => "weapon",
+        ItemCategory::Armor | ItemCategory::Shield => "equipment",
+        ItemCategory::Container => "container",
+        ItemCategory::Gear => "loot",
+    }
+}
+
+/// Returns the actor `sheet` is converted to
+pub fn actor(sheet: &CharacterSheet) -> Value {
+    json!({
+        "name": sheet.name(),
+        "type": "character",
+        "system": {
+            "abilities": abilities(sheet),
+            "attributes": attributes(sheet),
+            "details": {
+                "xp": { "value": sheet.experience() },
+            },
+            "skills": skills(sheet),
+            "traits": {
+                "size": size_key(sheet.size()),
+                "languages": { "value": sheet.languages(), "custom": "" },
+            },
+        },
+        "items": items(sheet),
+    })
+}
+
+/// Returns `sheet` as a pretty printed actor
+pub fn to_string(sheet: &CharacterSheet) -> String {
+    format!("{:#}", actor(sheet))
+}
+
+fn abilities(sheet: &CharacterSheet) -> Value {
+    Ability::ALL
+        .iter()
+        .map(|&ability| {
+            (
+                String::from(ability_key(ability)),
+                json!({
+                    "value": sheet.ability_score(ability),
+                    "proficient": u8::from(sheet.is_proficient(&ability.proficiency())),
+                }),
+            )
+        })
+        .collect::<Map<String, Value>>()
+        .into()
+}
+
+fn skills(sheet: &CharacterSheet) -> Value {
+    Skill::ALL
+        .iter()
+        .map(|&skill| {
+            let proficient = u8::from(sheet.is_proficient(&skill.proficiency()));
+            (
+                String::from(skill_key(skill)),
+                json!({ "value": proficient, "ability": ability_key(skill.ability()) }),
+            )
+        })
+        .collect::<Map<String, Value>>()
+        .into()
+}
+
+fn attributes(sheet: &CharacterSheet) -> Value {
+    let mut movement: Map<String, Value> = [
+        Movement::Walk,
+        Movement::Fly,
+        Movement::Climb,
+        Movement::Swim,
+        Movement::Burrow,
+    ]
+    .iter()
+    .map(|&m| {
+        let speed = sheet.speeds().get(&m).map_or(0, |speed| speed.feet());
+        (String::from(movement_key(m)), json!(speed))
+    })
+    .collect();
+    movement.insert(String::from("units"), json!("ft"));
+
+    let mut senses: Map<String, Value> = SENSES
+        .iter()
+        .map(|&id| {
+            let range = sheet.senses().range(id).flatten();
+            (String::from(id), json!(range.map_or(0, |r| r.feet())))
+        })
+        .collect();
+    let special: Vec<String> = sheet
+        .senses()
+        .ranges()
+        .iter()
+        .filter(|(id, _)| !SENSES.contains(&id.as_str()))
+        .map(|(id, range)| match range {
+            Some(range) => format!("{} {}", id, range),
+            None => id.clone(),
+        })
+        .collect();
+    senses.insert(String::from("special"), json!(special.join(", ")));
+    senses.insert(String::from("units"), json!("ft"));
+
+    json!({
+        "ac": { "calc": "flat", "flat": sheet.armor_class() },
+        "hp": { "value": sheet.hit_points(), "max": sheet.hit_points(), "temp": 0 },
+        "movement": movement,
+        "senses": senses,
+    })
+}
+
+/// Returns the damage parts of a weapon item, from the attack made with it
+fn damage(attack: &Attack) -> Value {
+    json!({
+        "parts": [[attack.damage.to_string(), attack.damage_type]],
+        "versatile": attack.two_handed_damage.as_ref().map_or(String::new(), |d| d.to_string()),
+    })
+}
+
+fn items(sheet: &CharacterSheet) -> Value {
+    let classes = sheet.hit_dice().iter().map(|hd| {
+        json!({
+            "name": hd.name,
+            "type": "class",
+            "system": {
+                "identifier": hd.class,
+                "levels": hd.count,
+                "hitDice": format!("d{}", hd.die.sides()),
+            },
+        })
+    });
+
+    let features = sheet.features().iter().map(|feature| {
+        json!({
+            "name": feature.name,
+            "type": "feat",
+            "system": {
+                "identifier": feature.id,
+                "description": { "value": feature.description.clone().unwrap_or_default() },
+            },
+        })
+    });
+
+    let spells = sheet.spells().iter().map(|id| {
+        json!({
+            "name": id,
+            "type": "spell",
+            "system": { "identifier": id },
+        })
+    });
+
+    let equipment = sheet.items().iter().map(|item| {
+        let mut system = json!({
+            "identifier": item.id,
+            "quantity": item.quantity,
+            "weight": item.weight,
+            "equipped": item.equipped,
+            "attuned": item.attuned,
+        });
+        if let Some(attack) = sheet.attacks().iter().find(|attack| attack.id == item.id) {
+            system["damage"] = damage(attack);
+        }
+        json!({
+            "name": item.name,
+            "type": item_type(item.category),
+            "system": system,
+        })
+    });
+
+    classes
+        .chain(features)
+        .chain(spells)
+        .chain(equipment)
+        .collect::<Vec<Value>>()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys() {
+        assert_eq!("str", ability_key(Ability::Strength));
+        assert_eq!("cha", ability_key(Ability::Charisma));
+        assert_eq!("slt", skill_key(Skill::SleightOfHand));
+        assert_eq!("med", size_key("medium"));
+        assert_eq!("tiny", size_key("tiny"));
+    }
+}
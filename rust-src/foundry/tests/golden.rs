@@ -0,0 +1,66 @@
+//! Golden tests: every Character in `tests/characters` is converted to an actor,
+//! which must match the JSON of the same name in `tests/golden`.
+//!
+//! Run with `UPDATE_GOLDEN=1` to write the actors instead, after a change to
+//! how sheets are computed, and review the diff.
+use std::fs;
+use std::path::Path;
+
+use validator::assets::character::Character;
+use validator::character_sheet::CharacterSheet;
+use validator::library::Library;
+
+fn library() -> Library {
+    let mut library = Library::new();
+    for path in [
+        "class/fighter.yml",
+        "background",
+        "feature",
+        "item",
+        "language.yml",
+        "size.yml",
+        "trait",
+        "vision.yml",
+    ] {
+        library
+            .add_path(&Path::new("../../data").join(path))
+            .unwrap();
+    }
+    library
+}
+
+#[test]
+fn golden() {
+    let library = library();
+    let update = ::std::env::var_os("UPDATE_GOLDEN").is_some();
+
+    let mut paths: Vec<_> = fs::read_dir("tests/characters")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty());
+
+    for path in paths {
+        let character: Character =
+            serde_yaml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let actor = foundry::to_string(&CharacterSheet::build(&character, &library)) + "\n";
+
+        let golden = Path::new("tests/golden")
+            .join(path.file_stem().unwrap())
+            .with_extension("json");
+        if update {
+            fs::write(&golden, &actor).unwrap();
+        } else {
+            let expected = fs::read_to_string(&golden)
+                .unwrap_or_else(|_| panic!("{} is missing", golden.display()));
+            assert_eq!(
+                expected,
+                actor,
+                "{} doesn't match {}",
+                path.display(),
+                golden.display()
+            );
+        }
+    }
+}
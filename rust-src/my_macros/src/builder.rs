@@ -22,13 +22,11 @@ pub fn impl_builder_macro(ast: &syn::DeriveInput) -> TokenStream {
 
         let fields = &struct_data.fields;
         let field_idents: Vec<&Option<Ident>> = struct_data.fields.iter().map(|v| &v.ident).collect();
-        let priv_field_idents: Vec<Option<Ident>> = struct_data.fields.iter().map(|v| -> Option<Ident> {
-            if let Some(id) = &v.ident {
-                Some(quote::format_ident!("set_{}", id))
-            } else {
-                None
-            }
-        }).collect();
+        let priv_field_idents: Vec<Option<Ident>> = struct_data
+            .fields
+            .iter()
+            .map(|v| v.ident.as_ref().map(|id| quote::format_ident!("set_{}", id)))
+            .collect();
 
         let field_tys: Vec<&syn::Type> = fields.iter().map(|v| &v.ty).collect();
 
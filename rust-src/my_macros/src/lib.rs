@@ -96,7 +96,7 @@ fn impl_selectenum_macro(ast: &syn::DeriveInput) -> TokenStream {
 
         let gen = quote! {
             /// SelectVariant
-            #[derive(Debug, PartialEq, Serialize)]
+            #[derive(Debug, PartialEq, Serialize, Clone, ::schemars::JsonSchema)]
             pub struct SelectVariant {
                 /// Pretty Name to be displayed
                 pub name: String,
@@ -142,7 +142,7 @@ fn impl_selectenum_macro(ast: &syn::DeriveInput) -> TokenStream {
             }
 
             /// Select
-            #[derive(Debug, PartialEq, Serialize, Deserialize)]
+            #[derive(Debug, PartialEq, Serialize, Deserialize, Clone, ::schemars::JsonSchema)]
             #[serde(rename_all = "lowercase", tag = "type")]
             pub enum Select {
                 #(#[doc = #variant_docs] #variants (SelectVariant),)*
@@ -226,7 +226,7 @@ fn impl_enumstring_macro(ast: &syn::DeriveInput) -> TokenStream {
             syn::Fields::Unit => quote! {},
             syn::Fields::Unnamed(fields) => {
                 let defaults =
-                    ::std::iter::repeat(quote!(Default::default())).take(fields.unnamed.len());
+                    ::std::iter::repeat_n(quote!(Default::default()), fields.unnamed.len());
                 quote! { (#(#defaults),*) }
             }
             syn::Fields::Named(fields) => {
@@ -1,5 +1,6 @@
 use my_macros::EnumString;
 
+#[allow(dead_code)]
 #[derive(EnumString)]
 enum E {
     Foo,
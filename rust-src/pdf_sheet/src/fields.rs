@@ -0,0 +1,185 @@
+//! Form fields of the fillable 5e character sheet, and their values for a sheet.
+//!
+//! Some of the field names have trailing spaces, as they do in the form.
+use std::collections::BTreeMap;
+
+use validator::assets::character::{Ability, Skill};
+use validator::assets::{title, Movement};
+use validator::character_sheet::CharacterSheet;
+
+/// Names of the score and modifier fields of each Ability
+fn ability_fields(ability: Ability) -> (&'static str, &'static str) {
+    match ability {
+        Ability::Strength => ("STR", "STRmod"),
+        Ability::Dexterity => ("DEX", "DEXmod "),
+        Ability::Constitution => ("CON", "CONmod"),
+        Ability::Intelligence => ("INT", "INTmod"),
+        Ability::Wisdom => ("WIS", "WISmod"),
+        Ability::Charisma => ("CHA", "CHamod"),
+    }
+}
+
+/// Name of the modifier field of each Skill
+fn skill_field(skill: Skill) -> &'static str {
+    match skill {
+        Skill::Acrobatics => "Acrobatics",
+        Skill::AnimalHandling => "Animal",
+        Skill::Arcana => "Arcana",
+        Skill::Athletics => "Athletics",
+        Skill::Deception => "Deception ",
+        Skill::History => "History ",
+        Skill::Insight => "Insight",
+        Skill::Intimidation => "Intimidation",
+        Skill::Investigation => "Investigation ",
+        Skill::Medicine => "Medicine",
+        Skill::Nature => "Nature",
+        Skill::Perception => "Perception ",
+        Skill::Performance => "Performance",
+        Skill::Persuasion => "Persuasion",
+        Skill::Religion => "Religion",
+        Skill::SleightOfHand => "SleightofHand",
+        Skill::Stealth => "Stealth ",
+        Skill::Survival => "Survival",
+    }
+}
+
+/// Name of the proficiency check box of each Skill
+fn skill_check_box(skill: Skill) -> &'static str {
+    match skill {
+        Skill::Acrobatics => "Check Box 23",
+        Skill::AnimalHandling => "Check Box 24",
+        Skill::Arcana => "Check Box 25",
+        Skill::Athletics => "Check Box 26",
+        Skill::Deception => "Check Box 27",
+        Skill::History => "Check Box 28",
+        Skill::Insight => "Check Box 29",
+        Skill::Intimidation => "Check Box 30",
+        Skill::Investigation => "Check Box 31",
+        Skill::Medicine => "Check Box 32",
+        Skill::Nature => "Check Box 33",
+        Skill::Perception => "Check Box 34",
+        Skill::Performance => "Check Box 35",
+        Skill::Persuasion => "Check Box 36",
+        Skill::Religion => "Check Box 37",
+        Skill::SleightOfHand => "Check Box 38",
+        Skill::Stealth => "Check Box 39",
+        Skill::Survival => "Check Box 40",
+    }
+}
+
+/// State of a ticked check box
+const CHECKED: &str = "Yes";
+
+/// Names of the name, attack bonus and damage fields of the three weapon rows
+const WEAPON_FIELDS: [(&str, &str, &str); 3] = [
+    ("Wpn Name", "Wpn1 AtkBonus", "Wpn1 Damage"),
+    ("Wpn Name 2", "Wpn2 AtkBonus ", "Wpn2 Damage "),
+    ("Wpn Name 3", "Wpn3 AtkBonus  ", "Wpn3 Damage "),
+];
+
+/// Returns the value of every form field `sheet` fills in, by the field's name
+pub fn fields(sheet: &CharacterSheet) -> BTreeMap<&'static str, String> {
+    let mut fields = BTreeMap::new();
+
+    let classes: Vec<String> = sheet
+        .hit_dice()
+        .iter()
+        .map(|hd| format!("{} {}", hd.name, hd.count))
+        .collect();
+    let hit_dice: Vec<String> = sheet
+        .hit_dice()
+        .iter()
+        .map(|hd| format!("{}d{}", hd.count, hd.die.sides()))
+        .collect();
+
+    fields.insert("CharacterName", sheet.name().to_string());
+    fields.insert("ClassLevel", classes.join(" / "));
+    fields.insert("XP", sheet.experience().to_string());
+    fields.insert("ProfBonus", format!("{:+}", sheet.proficiency_bonus()));
+    fields.insert("AC", sheet.armor_class().to_string());
+    fields.insert(
+        "Initiative",
+        format!("{:+}", sheet.ability_modifier(Ability::Dexterity)),
+    );
+    fields.insert(
+        "Speed",
+        sheet
+            .speeds()
+            .get(&Movement::Walk)
+            .map_or(0, |speed| speed.feet())
+            .to_string(),
+    );
+    fields.insert("HPMax", sheet.hit_points().to_string());
+    fields.insert("HDTotal", sheet.level().to_string());
+    fields.insert("HD", hit_dice.join(", "));
+    fields.insert("Passive", sheet.senses().passive_perception().to_string());
+
+    for ability in Ability::ALL {
+        let (score, modifier) = ability_fields(ability);
+        fields.insert(score, sheet.ability_score(ability).to_string());
+        fields.insert(modifier, format!("{:+}", sheet.ability_modifier(ability)));
+    }
+
+    for skill in Skill::ALL {
+        fields.insert(
+            skill_field(skill),
+            format!("{:+}", sheet.skill_modifier(skill)),
+        );
+        if sheet.skills().get(&skill).is_some_and(|check| check.proficient) {
+            fields.insert(skill_check_box(skill), String::from(CHECKED));
+        }
+    }
+
+    for ((name, bonus, damage), attack) in WEAPON_FIELDS.iter().zip(sheet.attacks()) {
+        fields.insert(name, attack.name.clone());
+        fields.insert(bonus, format!("{:+}", attack.bonus));
+        fields.insert(damage, format!("{} {}", attack.damage, attack.damage_type));
+    }
+
+    let languages = sheet
+        .languages()
+        .iter()
+        .map(|id| title(id))
+        .collect::<Vec<_>>()
+        .join(", ");
+    fields.insert("ProficienciesLang", format!("Languages: {}", languages));
+    fields.insert(
+        "Features and Traits",
+        sheet
+            .features()
+            .iter()
+            .map(|feature| feature.name.clone())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+    fields.insert(
+        "Equipment",
+        sheet
+            .items()
+            .iter()
+            .map(|item| format!("{} x{}", item.name, item.quantity))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_skill_has_a_field() {
+        let mut names: Vec<&str> = Skill::ALL.iter().map(|&s| skill_field(s)).collect();
+        names.sort();
+        names.dedup();
+
+        assert_eq!(Skill::ALL.len(), names.len());
+
+        let mut names: Vec<&str> = Skill::ALL.iter().map(|&s| skill_check_box(s)).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(Skill::ALL.len(), names.len());
+    }
+}
@@ -0,0 +1,503 @@
+//! Writes a Character Sheet to a PDF, for players that want it printed.
+//!
+//! With a template, such as the fillable 5e character sheet, the AcroForm
+//! fields of the template are filled in. Without one, a plain sheet is laid out.
+#![warn(missing_docs)]
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+
+use lopdf::content::{Content, Operation};
+use lopdf::{dictionary, Document, Object, ObjectId, Stream, StringFormat};
+use validator::assets::character::{Ability, Skill};
+use validator::assets::{title, Movement};
+use validator::character_sheet::CharacterSheet;
+
+pub mod fields;
+
+/// Error for writing a PDF
+#[derive(Debug)]
+pub enum PdfError {
+    /// The PDF couldn't be read or written
+    Pdf(lopdf::Error),
+    /// The template has no form to fill in
+    NoForm,
+}
+
+impl fmt::Display for PdfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Pdf(e) => write!(f, "{}", e),
+            Self::NoForm => write!(f, "the template has no form fields"),
+        }
+    }
+}
+
+impl ::std::error::Error for PdfError {}
+
+impl From<lopdf::Error> for PdfError {
+    fn from(e: lopdf::Error) -> Self {
+        Self::Pdf(e)
+    }
+}
+
+impl From<io::Error> for PdfError {
+    fn from(e: io::Error) -> Self {
+        Self::Pdf(e.into())
+    }
+}
+
+/// Writes `sheet` as a PDF to `writer`, filling in the form of the PDF at
+/// `template`, or laying out a plain sheet if there is no template
+pub fn write<W: Write>(
+    sheet: &CharacterSheet,
+    template: Option<&Path>,
+    writer: &mut W,
+) -> Result<(), PdfError> {
+    let mut document = match template {
+        Some(path) => fill(Document::load(path)?, &fields::fields(sheet))?,
+        None => generate(sheet)?,
+    };
+
+    document.save_to(writer)?;
+
+    Ok(())
+}
+
+/// Returns `document` with each form field named in `values` set to its value.
+///
+/// Check boxes are set to the state named by their value, such as `Yes`.
+/// Fields in `document` that aren't in `values` are left as they are.
+pub fn fill(mut document: Document, values: &BTreeMap<&str, String>) -> Result<Document, PdfError> {
+    let form = match document.catalog()?.get(b"AcroForm") {
+        Ok(Object::Reference(id)) => document.get_object(*id)?.as_dict()?,
+        Ok(Object::Dictionary(form)) => form,
+        _ => return Err(PdfError::NoForm),
+    };
+
+    let mut ids: Vec<(ObjectId, String)> = vec![];
+    let mut visited = HashSet::new();
+    for field in document.dereference(form.get(b"Fields")?)?.1.as_array()? {
+        named_fields(&document, field.as_reference()?, "", &mut ids, &mut visited)?;
+    }
+
+    for (id, name) in ids {
+        if let Some(value) = values.get(name.as_str()) {
+            let field = document.get_object_mut(id)?.as_dict_mut()?;
+            if matches!(field.get(b"FT").and_then(Object::as_name), Ok(b"Btn")) {
+                field.set("V", Object::Name(value.clone().into_bytes()));
+                field.set("AS", Object::Name(value.clone().into_bytes()));
+            } else {
+                field.set("V", text_string(value));
+            }
+        }
+    }
+
+    // Viewers draw the fields from their values, as they have no appearance yet
+    match document.catalog_mut()?.get_mut(b"AcroForm")? {
+        Object::Dictionary(form) => form.set("NeedAppearances", true),
+        Object::Reference(id) => {
+            let id = *id;
+            document
+                .get_object_mut(id)?
+                .as_dict_mut()?
+                .set("NeedAppearances", true)
+        }
+        _ => {}
+    }
+
+    Ok(document)
+}
+
+/// Returns `text` as a PDF text string, which is read as PDFDocEncoding unless
+/// it starts with a byte order mark. ASCII is the same in PDFDocEncoding, and
+/// anything else is written as UTF-16BE.
+fn text_string(text: &str) -> Object {
+    if text.is_ascii() {
+        return Object::string_literal(text);
+    }
+
+    let bytes = [0xFE, 0xFF]
+        .into_iter()
+        .chain(text.encode_utf16().flat_map(u16::to_be_bytes))
+        .collect();
+    Object::String(bytes, StringFormat::Hexadecimal)
+}
+
+/// Adds the field `id` and its kids to `ids`, with their fully qualified names.
+///
+/// Fields already in `visited` are skipped, so a form whose kids loop back on
+/// themselves can still be filled.
+fn named_fields(
+    document: &Document,
+    id: ObjectId,
+    parent: &str,
+    ids: &mut Vec<(ObjectId, String)>,
+    visited: &mut HashSet<ObjectId>,
+) -> Result<(), PdfError> {
+    if !visited.insert(id) {
+        return Ok(());
+    }
+    let field = document.get_object(id)?.as_dict()?;
+
+    let name = match field.get(b"T").and_then(Object::as_str) {
+        Ok(partial) if parent.is_empty() => String::from_utf8_lossy(partial).into_owned(),
+        Ok(partial) => format!("{}.{}", parent, String::from_utf8_lossy(partial)),
+        Err(_) => String::from(parent),
+    };
+
+    let kids = field.get(b"Kids").and_then(|kids| document.dereference(kids));
+    if let Ok((_, Object::Array(kids))) = kids {
+        for kid in kids {
+            named_fields(document, kid.as_reference()?, &name, ids, visited)?;
+        }
+    }
+
+    ids.push((id, name));
+
+    Ok(())
+}
+
+/// Encoding of the font of the generated sheet
+const FONT_ENCODING: &str = "WinAnsiEncoding";
+
+/// Font size of the generated sheet, in points
+const FONT_SIZE: i64 = 11;
+
+/// Space between lines of the generated sheet, in points
+const LEADING: i64 = 15;
+
+/// Space around the generated sheet, in points
+const MARGIN: i64 = 50;
+
+/// Width and height of a Letter page, in points
+const PAGE_SIZE: (i64, i64) = (612, 792);
+
+/// Returns the lines of text on a generated sheet
+fn lines(sheet: &CharacterSheet) -> Vec<String> {
+    let mut lines = vec![
+        sheet.name().to_string(),
+        format!(
+            "Level {}    Experience {}    Proficiency Bonus +{}",
+            sheet.level(),
+            sheet.experience(),
+            sheet.proficiency_bonus()
+        ),
+        format!(
+            "Armor Class {}    Hit Points {}    Speed {}ft    Passive Perception {}",
+            sheet.armor_class(),
+            sheet.hit_points(),
+            sheet
+                .speeds()
+                .get(&Movement::Walk)
+                .map_or(0, |speed| speed.feet()),
+            sheet.senses().passive_perception()
+        ),
+        String::new(),
+    ];
+
+    lines.extend(Ability::ALL.iter().map(|&ability| {
+        format!(
+            "{:<14}{:>3}  ({:+})",
+            title(ability.name()),
+            sheet.ability_score(ability),
+            sheet.ability_modifier(ability)
+        )
+    }));
+    lines.push(String::new());
+
+    lines.extend(Skill::ALL.iter().map(|&skill| {
+        let proficient = if sheet.is_proficient(&skill.proficiency()) {
+            "*"
+        } else {
+            " "
+        };
+        format!(
+            "{} {:<18}{:+}",
+            proficient,
+            title(skill.name()),
+            sheet.skill_modifier(skill)
+        )
+    }));
+    lines.push(String::new());
+
+    lines.extend(sheet.attacks().iter().map(|attack| {
+        format!(
+            "{}: {:+} to hit, {} {}",
+            attack.name, attack.bonus, attack.damage, attack.damage_type
+        )
+    }));
+    lines.push(String::new());
+
+    lines.push(format!("Languages: {}", sheet.languages().join(", ")));
+    lines.extend(
+        sheet
+            .features()
+            .iter()
+            .map(|f| format!("Feature: {}", f.name)),
+    );
+    lines.extend(
+        sheet
+            .items()
+            .iter()
+            .map(|item| format!("Item: {} x{}", item.name, item.quantity)),
+    );
+
+    lines
+}
+
+/// Returns a plain sheet laid out from `sheet`, over as many pages as it needs
+pub fn generate(sheet: &CharacterSheet) -> Result<Document, PdfError> {
+    let mut document = Document::with_version("1.5");
+    let pages_id = document.new_object_id();
+
+    // Text shown with the font is in its encoding, not a text string, so
+    // characters outside of WinAnsiEncoding are left out
+    let font_id = document.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type1",
+        "BaseFont" => "Courier",
+        "Encoding" => FONT_ENCODING,
+    });
+    let resources_id = document.add_object(dictionary! {
+        "Font" => dictionary! {
+            "F1" => font_id,
+        },
+    });
+
+    let lines_per_page = ((PAGE_SIZE.1 - 2 * MARGIN) / LEADING) as usize;
+    let mut kids: Vec<Object> = vec![];
+    for page in lines(sheet).chunks(lines_per_page) {
+        let mut operations = vec![
+            Operation::new("BT", vec![]),
+            Operation::new("Tf", vec!["F1".into(), FONT_SIZE.into()]),
+            Operation::new("TL", vec![LEADING.into()]),
+            Operation::new("Td", vec![MARGIN.into(), (PAGE_SIZE.1 - MARGIN).into()]),
+        ];
+        for line in page {
+            operations.push(Operation::new(
+                "Tj",
+                vec![Object::string_literal(Document::encode_text(
+                    Some(FONT_ENCODING),
+                    line,
+                ))],
+            ));
+            operations.push(Operation::new("T*", vec![]));
+        }
+        operations.push(Operation::new("ET", vec![]));
+
+        let content = Content { operations };
+        let content_id = document.add_object(Stream::new(dictionary! {}, content.encode()?));
+        kids.push(
+            document
+                .add_object(dictionary! {
+                    "Type" => "Page",
+                    "Parent" => pages_id,
+                    "Contents" => content_id,
+                })
+                .into(),
+        );
+    }
+
+    let count = kids.len() as i64;
+    document.objects.insert(
+        pages_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => kids,
+            "Count" => count,
+            "Resources" => resources_id,
+            "MediaBox" => vec![0.into(), 0.into(), PAGE_SIZE.0.into(), PAGE_SIZE.1.into()],
+        }),
+    );
+
+    let catalog_id = document.add_object(dictionary! {
+        "Type" => "Catalog",
+        "Pages" => pages_id,
+    });
+    document.trailer.set("Root", catalog_id);
+
+    Ok(document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validator::test_util;
+
+    fn sheet() -> CharacterSheet {
+        test_util::sheet("foobar")
+    }
+
+    /// Returns a form with a text field for each of `names`
+    fn template(names: &[&str]) -> Document {
+        let mut document = Document::with_version("1.5");
+        let fields: Vec<Object> = names
+            .iter()
+            .map(|&name| {
+                document
+                    .add_object(dictionary! {
+                        "FT" => "Tx",
+                        "T" => Object::string_literal(name),
+                    })
+                    .into()
+            })
+            .collect();
+        form(document, fields)
+    }
+
+    /// Returns `document` with a form of `fields`, which is referred to indirectly
+    /// as it is in most fillable PDFs
+    fn form(mut document: Document, fields: Vec<Object>) -> Document {
+        let fields_id = document.add_object(fields);
+        let form_id = document.add_object(dictionary! { "Fields" => fields_id });
+        let catalog_id = document.add_object(dictionary! {
+            "Type" => "Catalog",
+            "AcroForm" => form_id,
+        });
+        document.trailer.set("Root", catalog_id);
+        document
+    }
+
+    fn value(document: &Document, name: &str) -> Option<String> {
+        document.objects.values().find_map(|object| {
+            let field = object.as_dict().ok()?;
+            if field.get(b"T").ok()?.as_str().ok()? != name.as_bytes() {
+                return None;
+            }
+            let value = field.get(b"V").ok()?.as_str().ok()?;
+            match value.strip_prefix(&[0xFE, 0xFF]) {
+                Some(utf16) => {
+                    let units: Vec<u16> = utf16
+                        .chunks(2)
+                        .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                        .collect();
+                    String::from_utf16(&units).ok()
+                }
+                None => Some(String::from_utf8_lossy(value).into_owned()),
+            }
+        })
+    }
+
+    #[test]
+    fn test_fill() {
+        let values = fields::fields(&sheet());
+        let document = fill(template(&["CharacterName", "STR", "Notes"]), &values).unwrap();
+
+        assert_eq!(
+            Some(String::from("foobar")),
+            value(&document, "CharacterName")
+        );
+        assert_eq!(Some(String::from("16")), value(&document, "STR"));
+        assert_eq!(None, value(&document, "Notes"));
+    }
+
+    #[test]
+    fn test_fill_check_box() {
+        let mut document = Document::with_version("1.5");
+        let athletics = document.add_object(dictionary! {
+            "FT" => "Btn",
+            "T" => Object::string_literal("Check Box 26"),
+            "AS" => "Off",
+        });
+        let arcana = document.add_object(dictionary! {
+            "FT" => "Btn",
+            "T" => Object::string_literal("Check Box 25"),
+            "AS" => "Off",
+        });
+        let document = form(document, vec![athletics.into(), arcana.into()]);
+
+        let document = fill(document, &fields::fields(&sheet())).unwrap();
+        let state = |id| {
+            let field = document.get_dictionary(id).unwrap();
+            field.get(b"AS").and_then(Object::as_name).unwrap().to_vec()
+        };
+
+        // The fighter is proficient in Athletics, but not Arcana
+        assert_eq!(b"Yes".to_vec(), state(athletics));
+        assert_eq!(b"Off".to_vec(), state(arcana));
+    }
+
+    #[test]
+    fn test_fill_cyclic_kids() {
+        let mut document = Document::with_version("1.5");
+        let parent = document.new_object_id();
+        let kid = document.add_object(dictionary! {
+            "FT" => "Tx",
+            "T" => Object::string_literal("STR"),
+            "Kids" => vec![parent.into()],
+        });
+        document.objects.insert(
+            parent,
+            Object::Dictionary(dictionary! {
+                "T" => Object::string_literal("Parent"),
+                "Kids" => vec![kid.into()],
+            }),
+        );
+        let document = form(document, vec![parent.into()]);
+
+        let values = BTreeMap::from([("Parent.STR", String::from("16"))]);
+        let document = fill(document, &values).unwrap();
+        assert_eq!(Some(String::from("16")), value(&document, "STR"));
+    }
+
+    #[test]
+    fn test_fill_non_ascii() {
+        let values = fields::fields(&test_util::sheet("Aëlar"));
+        let document = fill(template(&["CharacterName", "STR"]), &values).unwrap();
+
+        assert_eq!(
+            Some(String::from("Aëlar")),
+            value(&document, "CharacterName")
+        );
+        assert_eq!(Some(String::from("16")), value(&document, "STR"));
+    }
+
+    #[test]
+    fn test_fill_without_form() {
+        let document = generate(&sheet()).unwrap();
+
+        assert!(matches!(
+            fill(document, &BTreeMap::new()),
+            Err(PdfError::NoForm)
+        ));
+    }
+
+    #[test]
+    fn test_write_template() {
+        let path = ::std::env::temp_dir().join("pdf_sheet_test_write_template.pdf");
+        template(&["CharacterName", "AC"])
+            .save_to(&mut ::std::fs::File::create(&path).unwrap())
+            .unwrap();
+
+        let mut pdf = vec![];
+        write(&sheet(), Some(&path), &mut pdf).unwrap();
+        let _ = ::std::fs::remove_file(&path);
+
+        let document = Document::load_mem(&pdf).unwrap();
+        assert_eq!(Some(String::from("11")), value(&document, "AC"));
+    }
+
+    #[test]
+    fn test_write_generated() {
+        let mut pdf = vec![];
+        write(&sheet(), None, &mut pdf).unwrap();
+
+        let document = Document::load_mem(&pdf).unwrap();
+        assert_eq!(1, document.get_pages().len());
+
+        let text = document.extract_text(&[1]).unwrap();
+        assert!(text.contains("foobar"));
+        assert!(text.contains("Longsword: +5 to hit, 1d8+3 slashing"));
+    }
+
+    #[test]
+    fn test_write_generated_non_ascii() {
+        let mut pdf = vec![];
+        write(&test_util::sheet("Aëlar"), None, &mut pdf).unwrap();
+
+        let document = Document::load_mem(&pdf).unwrap();
+        assert!(document.extract_text(&[1]).unwrap().contains("Aëlar"));
+    }
+}
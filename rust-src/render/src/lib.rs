@@ -0,0 +1,169 @@
+//! Renders a Character Sheet as Markdown or as a standalone HTML page, for
+//! sharing in chat and wikis.
+//!
+//! Both are rendered from [minijinja] templates, which can be swapped out to
+//! change the layout. Templates get the sheet as `sheet`, a [`view::SheetView`],
+//! and have the `signed`, `title` and `escape_markdown` filters on top of the
+//! built-in ones.
+#![warn(missing_docs)]
+use minijinja::{context, Environment};
+use validator::assets::title;
+use validator::character_sheet::CharacterSheet;
+
+pub mod view;
+
+pub use minijinja::Error;
+
+use view::SheetView;
+
+/// A format a sheet can be rendered in
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    /// Markdown
+    Markdown,
+    /// A standalone HTML page
+    Html,
+}
+
+impl Format {
+    /// Returns the name of the template of the format.
+    ///
+    /// HTML is escaped in templates with a name ending in `.html`.
+    fn template(&self) -> &'static str {
+        match self {
+            Format::Markdown => "sheet.md",
+            Format::Html => "sheet.html",
+        }
+    }
+}
+
+/// Returns `modifier` with its sign, such as `+2`
+fn signed(modifier: i64) -> String {
+    format!("{:+}", modifier)
+}
+
+/// Returns `text` with a backslash before every character that Markdown would
+/// read as a table cell, emphasis, code, a link or HTML, such as the `|` in a name
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '|' | '<' | '>' | '*' | '_' | '`' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Renders sheets from templates
+pub struct Renderer<'source> {
+    env: Environment<'source>,
+}
+
+impl Default for Renderer<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'source> Renderer<'source> {
+    /// Returns a Renderer with the default templates
+    pub fn new() -> Self {
+        let mut env = Environment::new();
+        env.add_filter("signed", signed);
+        env.add_filter("title", title);
+        env.add_filter("escape_markdown", escape_markdown);
+
+        // The default templates are rendered by the tests, so they always parse
+        env.add_template("sheet.md", include_str!("../templates/sheet.md"))
+            .expect("the default Markdown template parses");
+        env.add_template("sheet.html", include_str!("../templates/sheet.html"))
+            .expect("the default HTML template parses");
+
+        Self { env }
+    }
+
+    /// Replaces the template of `format` with `source`, returning an error if
+    /// it doesn't parse
+    pub fn set_template(&mut self, format: Format, source: &'source str) -> Result<(), Error> {
+        self.env.add_template(format.template(), source)
+    }
+
+    /// Renders `sheet` in `format`
+    pub fn render(&self, sheet: &CharacterSheet, format: Format) -> Result<String, Error> {
+        self.env
+            .get_template(format.template())?
+            .render(context! { sheet => SheetView::from(sheet) })
+    }
+}
+
+/// Renders `sheet` as Markdown with the default template
+pub fn markdown(sheet: &CharacterSheet) -> Result<String, Error> {
+    Renderer::new().render(sheet, Format::Markdown)
+}
+
+/// Renders `sheet` as a standalone HTML page with the default template
+pub fn html(sheet: &CharacterSheet) -> Result<String, Error> {
+    Renderer::new().render(sheet, Format::Html)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validator::test_util;
+
+    fn sheet() -> CharacterSheet {
+        test_util::sheet("<Batman>")
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = markdown(&sheet()).unwrap();
+
+        assert!(markdown.starts_with("# \\<Batman\\>\n"));
+        assert!(markdown.contains("Fighter 2, level 2"));
+        assert!(markdown.contains("| Strength | 16 | +3 |"));
+        assert!(markdown.contains("| **Athletics** | STR | +5 |"));
+        assert!(markdown.contains("| Sleight of Hand | DEX | +1 |"));
+        assert!(markdown.contains("| Longsword | +5 | 1d8+3 slashing (1d10+3 two-handed) |"));
+        assert!(markdown.contains("### Fey Step\n\nYou can cast the misty step spell"));
+        assert!(markdown.contains("- Misty Step\n"));
+        assert!(markdown.contains("- Rations (1 day) x3\n"));
+    }
+
+    #[test]
+    fn test_markdown_escapes_names() {
+        let markdown = markdown(&test_util::sheet("Bat|man *the* [Dark]")).unwrap();
+
+        assert!(markdown.starts_with("# Bat\\|man \\*the\\* \\[Dark\\]\n"));
+        assert_eq!("a\\\\b \\_c\\_ \\`d\\`", escape_markdown("a\\b _c_ `d`"));
+    }
+
+    #[test]
+    fn test_html() {
+        let html = html(&sheet()).unwrap();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>&lt;Batman&gt;</title>"));
+        assert!(html.contains("<tr class=\"proficient\"><td>Athletics</td>"));
+        assert!(html.contains("<li>Misty Step</li>"));
+        assert!(html.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn test_custom_template() {
+        let mut renderer = Renderer::new();
+        renderer
+            .set_template(
+                Format::Markdown,
+                "{{ sheet.name }}: AC {{ sheet.armor_class }}, {{ sheet.hit_points }} HP",
+            )
+            .unwrap();
+
+        assert_eq!(
+            "<Batman>: AC 11, 20 HP",
+            renderer.render(&sheet(), Format::Markdown).unwrap()
+        );
+        assert!(renderer.set_template(Format::Html, "{% if %}").is_err());
+    }
+}
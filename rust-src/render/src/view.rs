@@ -0,0 +1,170 @@
+//! What templates see of a Character Sheet.
+use serde_derive::Serialize;
+use validator::assets::character::{Ability, Skill};
+use validator::character_sheet::CharacterSheet;
+
+/// A Character Sheet, as templates see it
+#[derive(Debug, PartialEq, Serialize)]
+pub struct SheetView {
+    /// Name of the Character
+    pub name: String,
+    /// Each class and its level, such as `Fighter 3`
+    pub classes: Vec<String>,
+    /// Total level
+    pub level: i8,
+    /// Experience points
+    pub experience: u32,
+    /// Proficiency bonus
+    pub proficiency_bonus: i8,
+    /// Armor Class
+    pub armor_class: i8,
+    /// Maximum hit points
+    pub hit_points: i32,
+    /// Each speed, such as `walk 30ft`
+    pub speeds: Vec<String>,
+    /// Each sense, such as `darkvision 60ft`
+    pub senses: Vec<String>,
+    /// Passive Perception
+    pub passive_perception: i8,
+    /// The six Abilities
+    pub abilities: Vec<AbilityView>,
+    /// Every Skill
+    pub skills: Vec<SkillView>,
+    /// Attacks with equipped weapons
+    pub attacks: Vec<AttackView>,
+    /// Unique IDs of the languages known
+    pub languages: Vec<String>,
+    /// Features and Traits
+    pub features: Vec<FeatureView>,
+    /// Unique IDs of the Spells known
+    pub spells: Vec<String>,
+    /// Items carried, such as `Rations (1 day) x5`
+    pub items: Vec<String>,
+}
+
+/// An Ability, as templates see it
+#[derive(Debug, PartialEq, Serialize)]
+pub struct AbilityView {
+    /// Name of the Ability, as written in assets
+    pub name: &'static str,
+    /// Ability Score
+    pub score: i8,
+    /// Modifier of the score
+    pub modifier: i8,
+}
+
+/// A Skill, as templates see it
+#[derive(Debug, PartialEq, Serialize)]
+pub struct SkillView {
+    /// Name of the Skill, as written in assets
+    pub name: &'static str,
+    /// Name of the Ability the Skill is checked with
+    pub ability: &'static str,
+    /// Modifier of checks with the Skill
+    pub modifier: i8,
+    /// Whether the Character is proficient in the Skill
+    pub proficient: bool,
+}
+
+/// An attack, as templates see it
+#[derive(Debug, PartialEq, Serialize)]
+pub struct AttackView {
+    /// Name of the weapon
+    pub name: String,
+    /// Bonus to the attack roll
+    pub bonus: i8,
+    /// Damage dealt on a hit
+    pub damage: String,
+    /// Damage dealt with two hands, for versatile weapons
+    pub two_handed_damage: Option<String>,
+    /// Type of the damage
+    pub damage_type: String,
+}
+
+/// A Feature or Trait, as templates see it
+#[derive(Debug, PartialEq, Serialize)]
+pub struct FeatureView {
+    /// Name of the Feature
+    pub name: String,
+    /// Description of the Feature, if its asset has one
+    pub description: Option<String>,
+}
+
+impl From<&CharacterSheet> for SheetView {
+    fn from(sheet: &CharacterSheet) -> Self {
+        Self {
+            name: sheet.name().to_string(),
+            classes: sheet
+                .hit_dice()
+                .iter()
+                .map(|hd| format!("{} {}", hd.name, hd.count))
+                .collect(),
+            level: sheet.level(),
+            experience: sheet.experience(),
+            proficiency_bonus: sheet.proficiency_bonus(),
+            armor_class: sheet.armor_class(),
+            hit_points: sheet.hit_points(),
+            speeds: sheet
+                .speeds()
+                .iter()
+                .map(|(movement, speed)| format!("{:?} {}", movement, speed).to_lowercase())
+                .collect(),
+            senses: sheet
+                .senses()
+                .ranges()
+                .iter()
+                .map(|(id, range)| match range {
+                    Some(range) => format!("{} {}", id, range),
+                    None => id.clone(),
+                })
+                .collect(),
+            passive_perception: sheet.senses().passive_perception(),
+            abilities: Ability::ALL
+                .iter()
+                .map(|&ability| AbilityView {
+                    name: ability.name(),
+                    score: sheet.ability_score(ability),
+                    modifier: sheet.ability_modifier(ability),
+                })
+                .collect(),
+            skills: Skill::ALL
+                .iter()
+                .map(|&skill| SkillView {
+                    name: skill.name(),
+                    ability: skill.ability().name(),
+                    modifier: sheet.skill_modifier(skill),
+                    proficient: sheet.is_proficient(&skill.proficiency()),
+                })
+                .collect(),
+            attacks: sheet
+                .attacks()
+                .iter()
+                .map(|attack| AttackView {
+                    name: attack.name.clone(),
+                    bonus: attack.bonus,
+                    damage: attack.damage.to_string(),
+                    two_handed_damage: attack.two_handed_damage.as_ref().map(|d| d.to_string()),
+                    damage_type: attack.damage_type.clone(),
+                })
+                .collect(),
+            languages: sheet.languages().to_vec(),
+            features: sheet
+                .features()
+                .iter()
+                .map(|feature| FeatureView {
+                    name: feature.name.clone(),
+                    description: feature.description.as_ref().map(|d| d.trim().to_string()),
+                })
+                .collect(),
+            spells: sheet.spells().to_vec(),
+            items: sheet
+                .items()
+                .iter()
+                .map(|item| match item.quantity {
+                    1 => item.name.clone(),
+                    quantity => format!("{} x{}", item.name, quantity),
+                })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,297 @@
+//! Writes a Character Sheet to a MapTool token (`.rptok`) file.
+//!
+//! An rptok file is a zip holding the token as XStream XML in `content.xml`,
+//! along with `properties.xml` saying which MapTool version wrote it.
+#![warn(missing_docs)]
+use std::fmt;
+use std::io::{Seek, Write};
+
+use validator::character_sheet::CharacterSheet;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+pub mod section;
+
+use section::{Abilities, Skills, Speeds, Summary, TokenSection};
+
+/// MapTool version the token is written for
+const MAPTOOL_VERSION: &str = "1.13.2";
+
+/// A property on a token, such as `Strength`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Property {
+    /// Name of the property, which MapTool doesn't care about the case of
+    pub name: String,
+    /// Value of the property
+    pub value: String,
+}
+
+impl Property {
+    /// Returns the property `name` set to `value`
+    pub fn new(name: impl Into<String>, value: impl fmt::Display) -> Self {
+        Self {
+            name: name.into(),
+            value: value.to_string(),
+        }
+    }
+}
+
+/// A macro button on a token
+#[derive(Debug, PartialEq, Clone)]
+pub struct Macro {
+    /// Text on the button
+    pub label: String,
+    /// Group the button is shown in
+    pub group: String,
+    /// MapTool macro script run when the button is clicked
+    pub command: String,
+}
+
+impl Macro {
+    /// Returns the macro `label` in `group`, running `command`
+    pub fn new(label: &str, group: &str, command: String) -> Self {
+        Self {
+            label: String::from(label),
+            group: String::from(group),
+            command,
+        }
+    }
+}
+
+/// A MapTool token
+#[derive(Debug, PartialEq, Clone)]
+pub struct Token {
+    /// Name of the token
+    pub name: String,
+    /// Properties, in the order they were added
+    pub properties: Vec<Property>,
+    /// Macro buttons, in the order they were added
+    pub macros: Vec<Macro>,
+}
+
+impl Token {
+    /// Returns a token named `name` without any properties or macros
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: String::from(name),
+            properties: vec![],
+            macros: vec![],
+        }
+    }
+
+    /// Adds the properties and macros of `section` to the token
+    pub fn add(&mut self, section: &dyn TokenSection) -> &mut Self {
+        self.properties.extend(section.properties());
+        self.macros.extend(section.macros());
+        self
+    }
+
+    /// Writes the token as an rptok file to `writer`
+    pub fn write<W: Write + Seek>(&self, writer: W) -> zip::result::ZipResult<W> {
+        let mut zip = ZipWriter::new(writer);
+        let options = FileOptions::default();
+
+        zip.start_file("content.xml", options)?;
+        zip.write_all(self.content().as_bytes())?;
+
+        zip.start_file("properties.xml", options)?;
+        zip.write_all(properties().as_bytes())?;
+
+        zip.finish()
+    }
+
+    /// Returns the token as the XML MapTool reads from `content.xml`
+    pub fn content(&self) -> String {
+        let properties: String = self
+            .properties
+            .iter()
+            .map(|property| {
+                format!(
+                    "      <entry>
+        <string>{}</string>
+        <net.rptools.CaseInsensitiveHashMap_-KeyValue>
+          <key>{}</key>
+          <value class=\"string\">{}</value>
+          <outer-class reference=\"../../../..\"/>
+        </net.rptools.CaseInsensitiveHashMap_-KeyValue>
+      </entry>
+",
+                    escape(&property.name.to_lowercase()),
+                    escape(&property.name),
+                    escape(&property.value)
+                )
+            })
+            .collect();
+
+        let macros: String = self
+            .macros
+            .iter()
+            .enumerate()
+            .map(|(index, button)| {
+                format!(
+                    "    <entry>
+      <int>{index}</int>
+      <net.rptools.maptool.model.MacroButtonProperties>
+        <saveLocation>Token</saveLocation>
+        <index>{index}</index>
+        <colorKey>default</colorKey>
+        <hotKey>None</hotKey>
+        <command>{}</command>
+        <label>{}</label>
+        <group>{}</group>
+        <sortby></sortby>
+        <autoExecute>true</autoExecute>
+        <includeLabel>false</includeLabel>
+        <applyToTokens>false</applyToTokens>
+        <fontColorKey>black</fontColorKey>
+        <fontSize>1.00em</fontSize>
+        <allowPlayerEdits>true</allowPlayerEdits>
+        <displayHotKey>true</displayHotKey>
+      </net.rptools.maptool.model.MacroButtonProperties>
+    </entry>
+",
+                    escape(&button.command),
+                    escape(&button.label),
+                    escape(&button.group),
+                    index = index + 1
+                )
+            })
+            .collect();
+
+        format!(
+            "<net.rptools.maptool.model.Token>
+  <name>{}</name>
+  <tokenType>PC</tokenType>
+  <propertyType>Basic</propertyType>
+  <propertyMapCI>
+    <store>
+{}    </store>
+  </propertyMapCI>
+  <macroPropertiesMap>
+{}  </macroPropertiesMap>
+</net.rptools.maptool.model.Token>
+",
+            escape(&self.name),
+            properties,
+            macros
+        )
+    }
+}
+
+/// Every section of the sheet is put on the token
+impl From<&CharacterSheet> for Token {
+    fn from(sheet: &CharacterSheet) -> Self {
+        let mut token = Token::new(sheet.name());
+
+        token
+            .add(&Summary(sheet))
+            .add(&Abilities(sheet))
+            .add(&Skills(sheet))
+            .add(&Speeds(sheet))
+            .add(sheet.senses())
+            .add(sheet.carrying());
+        for attack in sheet.attacks() {
+            token.add(attack);
+        }
+
+        token
+    }
+}
+
+/// Returns the XML MapTool reads from `properties.xml`
+fn properties() -> String {
+    format!(
+        "<map>
+  <entry>
+    <string>version</string>
+    <string>{}</string>
+  </entry>
+</map>
+",
+        MAPTOOL_VERSION
+    )
+}
+
+/// Returns `text` with the characters XML gives a meaning to escaped
+fn escape(text: &str) -> String {
+    text.chars().fold(String::new(), |mut escaped, c| {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+        escaped
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Read};
+    use validator::test_util;
+
+    fn sheet() -> CharacterSheet {
+        test_util::sheet("Tom & Jerry")
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            "[r: 1d20+5] &lt;b&gt;&amp;&quot;&apos;",
+            escape("[r: 1d20+5] <b>&\"'")
+        );
+    }
+
+    #[test]
+    fn test_token_from_sheet() {
+        let token = Token::from(&sheet());
+        let property = |name: &str| {
+            token
+                .properties
+                .iter()
+                .find(|p| p.name == name)
+                .map(|p| p.value.as_str())
+        };
+
+        assert_eq!("Tom & Jerry", token.name);
+        assert_eq!(Some("16"), property("Strength"));
+        assert_eq!(Some("5"), property("Athletics"));
+        assert_eq!(Some("30"), property("Speed"));
+        assert_eq!(Some("10"), property("PassivePerception"));
+        assert_eq!(Some("Medium"), property("Size"));
+
+        let longsword = token
+            .macros
+            .iter()
+            .find(|m| m.label == "Longsword")
+            .unwrap();
+        assert_eq!("Attacks", longsword.group);
+        assert_eq!(
+            "Longsword: [r: 1d20+5] to hit, [r: 1d8+3] slashing damage, or [r: 1d10+3] with two hands",
+            longsword.command
+        );
+    }
+
+    #[test]
+    fn test_token_write() {
+        let token = Token::from(&sheet());
+        let rptok = token.write(Cursor::new(vec![])).unwrap().into_inner();
+
+        let mut zip = zip::ZipArchive::new(Cursor::new(rptok)).unwrap();
+        let mut content = String::new();
+        zip.by_name("content.xml")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+
+        assert_eq!(token.content(), content);
+        assert!(content.contains("<name>Tom &amp; Jerry</name>"));
+        assert!(content.contains("<key>Strength</key>"));
+        assert!(content.contains("<value class=\"string\">16</value>"));
+        assert!(content.contains("<label>Longsword</label>"));
+        assert!(zip.by_name("properties.xml").is_ok());
+    }
+}
@@ -0,0 +1,195 @@
+//! Sections of a Character Sheet, and what each puts on a token.
+use validator::assets::character::{Ability, Skill};
+use validator::assets::{title, Movement};
+use validator::character_sheet::{Attack, Carrying, CharacterSheet, Senses};
+
+use crate::{Macro, Property};
+
+/// A section of a Character Sheet that puts properties and macros on a token
+pub trait TokenSection {
+    /// Returns the token properties of the section
+    fn properties(&self) -> Vec<Property> {
+        vec![]
+    }
+
+    /// Returns the macros of the section
+    fn macros(&self) -> Vec<Macro> {
+        vec![]
+    }
+}
+
+/// Returns `id`, such as `sleight-of-hand`, as a property name such as `SleightOfHand`.
+///
+/// This is its [`title`] without spaces, as property names can't have any, and
+/// with every word capitalised so that each one still stands out.
+pub(crate) fn property_name(id: &str) -> String {
+    title(id)
+        .split(' ')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars.next().into_iter().flat_map(char::to_uppercase).chain(chars)
+        })
+        .collect()
+}
+
+/// Returns `modifier` with its sign, as it is added to a roll
+fn signed(modifier: i8) -> String {
+    format!("{:+}", modifier)
+}
+
+/// Name, level and the numbers everything else is built on
+pub struct Summary<'a>(pub &'a CharacterSheet);
+
+impl TokenSection for Summary<'_> {
+    fn properties(&self) -> Vec<Property> {
+        let sheet = self.0;
+
+        vec![
+            Property::new("Level", sheet.level()),
+            Property::new("ProficiencyBonus", sheet.proficiency_bonus()),
+            Property::new("ArmorClass", sheet.armor_class()),
+            Property::new("Size", property_name(sheet.size())),
+            Property::new("Languages", sheet.languages().join(", ")),
+        ]
+    }
+}
+
+/// The six Ability Scores, with a check for each
+pub struct Abilities<'a>(pub &'a CharacterSheet);
+
+impl TokenSection for Abilities<'_> {
+    fn properties(&self) -> Vec<Property> {
+        Ability::ALL
+            .iter()
+            .map(|&ability| {
+                Property::new(property_name(ability.name()), self.0.ability_score(ability))
+            })
+            .collect()
+    }
+
+    fn macros(&self) -> Vec<Macro> {
+        Ability::ALL
+            .iter()
+            .map(|&ability| {
+                let name = property_name(ability.name());
+                Macro::new(
+                    &name,
+                    "Checks",
+                    format!(
+                        "{} check: [r: 1d20{}]",
+                        name,
+                        signed(self.0.ability_modifier(ability))
+                    ),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Every Skill, with a check for each
+pub struct Skills<'a>(pub &'a CharacterSheet);
+
+impl TokenSection for Skills<'_> {
+    fn properties(&self) -> Vec<Property> {
+        Skill::ALL
+            .iter()
+            .map(|&skill| Property::new(property_name(skill.name()), self.0.skill_modifier(skill)))
+            .collect()
+    }
+
+    fn macros(&self) -> Vec<Macro> {
+        Skill::ALL
+            .iter()
+            .map(|&skill| {
+                let name = property_name(skill.name());
+                Macro::new(
+                    &name,
+                    "Skills",
+                    format!(
+                        "{} check: [r: 1d20{}]",
+                        name,
+                        signed(self.0.skill_modifier(skill))
+                    ),
+                )
+            })
+            .collect()
+    }
+}
+
+/// The speed of every way the Character can move
+pub struct Speeds<'a>(pub &'a CharacterSheet);
+
+impl TokenSection for Speeds<'_> {
+    fn properties(&self) -> Vec<Property> {
+        self.0
+            .speeds()
+            .iter()
+            .map(|(movement, speed)| {
+                let name = match movement {
+                    Movement::Walk => "Speed",
+                    Movement::Fly => "FlySpeed",
+                    Movement::Climb => "ClimbSpeed",
+                    Movement::Swim => "SwimSpeed",
+                    Movement::Burrow => "BurrowSpeed",
+                };
+                Property::new(name, speed.feet())
+            })
+            .collect()
+    }
+}
+
+impl TokenSection for Senses {
+    fn properties(&self) -> Vec<Property> {
+        self.ranges()
+            .iter()
+            .map(|(id, range)| {
+                let value = range.map(|r| r.feet().to_string()).unwrap_or_default();
+                Property::new(property_name(id), value)
+            })
+            .chain([Property::new(
+                "PassivePerception",
+                self.passive_perception(),
+            )])
+            .collect()
+    }
+}
+
+impl TokenSection for Carrying {
+    fn properties(&self) -> Vec<Property> {
+        vec![
+            Property::new("Weight", self.weight()),
+            Property::new("CarryingCapacity", self.capacity()),
+        ]
+    }
+}
+
+impl TokenSection for Attack {
+    fn macros(&self) -> Vec<Macro> {
+        let mut command = format!(
+            "{}: [r: 1d20{}] to hit, [r: {}] {} damage",
+            self.name,
+            signed(self.bonus),
+            self.damage,
+            self.damage_type
+        );
+        if let Some(damage) = &self.two_handed_damage {
+            command += &format!(", or [r: {}] with two hands", damage);
+        }
+
+        vec![Macro::new(&self.name, "Attacks", command)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_property_name() {
+        assert_eq!("SleightOfHand", property_name("sleight-of-hand"));
+        assert_eq!("TheEvilOfTheElvish", property_name("the-evil-of-the-elvish"));
+        assert_eq!("Strength", property_name("strength"));
+        assert_eq!("ArmoredAc", property_name("armored.ac"));
+        assert_eq!("", property_name(""));
+    }
+}
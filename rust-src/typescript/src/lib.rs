@@ -0,0 +1,356 @@
+//! Generates the TypeScript definitions of the models the frontend shares
+//! with the validator, so they come from the Rust types instead of being
+//! written twice.
+//!
+//! The definitions are generated from the JSON Schema of the Rust types. Each
+//! type they reference is declared too, under the name of its Rust type.
+#![warn(missing_docs)]
+use schemars::gen::SchemaSettings;
+use serde_json::{Map, Value};
+use validator::assets::character::{Character, CharacterAssetGrant};
+use validator::assets::Asset;
+use validator::character_sheet::CharacterSheet;
+
+/// Comment at the top of the generated definitions
+const HEADER: &str = "\
+// Generated from the Rust models by the `typescript` crate, do not edit.
+// Regenerate with `npm run types`.
+";
+
+/// Indentation of each level of nesting
+const INDENT: &str = "  ";
+
+/// Unions longer than this are written one variant per line
+const MAX_LINE: usize = 80;
+
+/// Returns the TypeScript definitions of [`Asset`], [`Character`],
+/// [`CharacterAssetGrant`] and [`CharacterSheet`], and of every type they reference
+pub fn definitions() -> serde_json::Result<String> {
+    let mut gen = SchemaSettings::draft07().into_generator();
+    gen.subschema_for::<Asset>();
+    gen.subschema_for::<Character>();
+    gen.subschema_for::<CharacterAssetGrant>();
+    gen.subschema_for::<CharacterSheet>();
+
+    let mut definitions = String::from(HEADER);
+    for (name, schema) in gen.definitions() {
+        let schema = serde_json::to_value(schema)?;
+        definitions.push('\n');
+        definitions.push_str(&declaration(name, &schema));
+    }
+
+    Ok(definitions)
+}
+
+/// Returns the declaration of the type `name` with the schema `schema`
+fn declaration(name: &str, schema: &Value) -> String {
+    let ts_type = ts_type(schema, "");
+    let separator = if ts_type.starts_with('\n') { "" } else { " " };
+
+    format!(
+        "{}export type {} ={}{};\n",
+        doc(schema, ""),
+        name,
+        separator,
+        ts_type
+    )
+}
+
+/// Returns the description of `schema` as a doc comment, if it has one
+fn doc(schema: &Value, indent: &str) -> String {
+    match schema.get("description").and_then(Value::as_str) {
+        Some(description) if description.contains('\n') => {
+            let lines: Vec<String> = description
+                .trim_end()
+                .lines()
+                .map(|line| format!("{} * {}", indent, line).trim_end().to_string())
+                .collect();
+            format!("{}/**\n{}\n{} */\n", indent, lines.join("\n"), indent)
+        }
+        Some(description) => format!("{}/** {} */\n", indent, description),
+        None => String::new(),
+    }
+}
+
+/// Returns the TypeScript type of a value matching `schema`, as written at `indent`
+fn ts_type(schema: &Value, indent: &str) -> String {
+    let schema = match schema {
+        Value::Object(schema) => schema,
+        _ => return String::from("unknown"),
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return reference.trim_start_matches("#/definitions/").to_string();
+    }
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return union(values.iter().map(Value::to_string).collect(), indent);
+    }
+    if let Some(value) = schema.get("const") {
+        return value.to_string();
+    }
+
+    // Variants are written below a `| ` if the union doesn't fit on one line
+    let variant_indent = format!("{}{}{}", indent, INDENT, INDENT);
+    let variants: Vec<String> = ["oneOf", "anyOf"]
+        .iter()
+        .filter_map(|key| schema.get(*key).and_then(Value::as_array))
+        .flatten()
+        .map(|variant| ts_type(variant, &variant_indent))
+        .collect();
+
+    let mut parts = vec![];
+    // An object that is only its variants, such as a flattened enum, is left
+    // to the variants
+    if variants.is_empty() || schema.contains_key("properties") {
+        match schema.get("type") {
+            Some(Value::String(instance)) => parts.push(instance_type(instance, schema, indent)),
+            Some(Value::Array(instances)) => parts.push(union(
+                instances
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(|instance| instance_type(instance, schema, &variant_indent))
+                    .collect(),
+                indent,
+            )),
+            _ => {}
+        }
+    }
+    if !variants.is_empty() {
+        parts.push(union(variants, indent));
+    }
+    if let Some(all) = schema.get("allOf").and_then(Value::as_array) {
+        parts.extend(all.iter().map(|s| ts_type(s, indent)));
+    }
+
+    match parts.len() {
+        0 => String::from("unknown"),
+        1 => parts.remove(0),
+        _ => parts
+            .iter()
+            .map(|part| match part.starts_with('{') || !part.contains('|') {
+                true => part.clone(),
+                false => format!("({})", part),
+            })
+            .collect::<Vec<_>>()
+            .join(" & "),
+    }
+}
+
+/// Returns the TypeScript type of the JSON type `instance`, for `schema`
+fn instance_type(instance: &str, schema: &Map<String, Value>, indent: &str) -> String {
+    match instance {
+        "string" => String::from("string"),
+        "integer" | "number" => String::from("number"),
+        "boolean" => String::from("boolean"),
+        "null" => String::from("null"),
+        "array" => match schema.get("items") {
+            Some(Value::Array(items)) => format!(
+                "[{}]",
+                items
+                    .iter()
+                    .map(|item| ts_type(item, indent))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Some(items) => format!("Array<{}>", ts_type(items, indent)),
+            None => String::from("Array<unknown>"),
+        },
+        "object" => object(schema, indent),
+        _ => String::from("unknown"),
+    }
+}
+
+/// Returns the TypeScript type of an object matching `schema`
+fn object(schema: &Map<String, Value>, indent: &str) -> String {
+    let additional = schema.get("additionalProperties");
+    let properties = match schema.get("properties").and_then(Value::as_object) {
+        Some(properties) => properties,
+        None => {
+            return match additional {
+                Some(values @ Value::Object(_)) => {
+                    format!("Record<string, {}>", ts_type(values, indent))
+                }
+                _ => String::from("Record<string, unknown>"),
+            }
+        }
+    };
+
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    let inner = format!("{}{}", indent, INDENT);
+    let mut object = String::from("{\n");
+    for (name, property) in properties {
+        object.push_str(&doc(property, &inner));
+        object.push_str(&format!(
+            "{}{}{}: {};\n",
+            inner,
+            property_name(name),
+            if required.contains(&name.as_str()) {
+                ""
+            } else {
+                "?"
+            },
+            ts_type(property, &inner)
+        ));
+    }
+    match additional {
+        Some(Value::Bool(true)) => object.push_str(&format!("{}[key: string]: unknown;\n", inner)),
+        Some(values @ Value::Object(_)) => object.push_str(&format!(
+            "{}[key: string]: {};\n",
+            inner,
+            ts_type(values, &inner)
+        )),
+        _ => {}
+    }
+    object.push_str(indent);
+    object.push('}');
+
+    object
+}
+
+/// Returns `name` as a property name, quoted if it isn't an identifier
+fn property_name(name: &str) -> String {
+    let identifier = !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+
+    match identifier {
+        true => name.to_string(),
+        false => format!("{:?}", name),
+    }
+}
+
+/// Returns a union of `types`, one per line if it doesn't fit on one
+fn union(mut types: Vec<String>, indent: &str) -> String {
+    types.dedup();
+    let line = types.join(" | ");
+
+    if types.len() == 1 || (line.len() <= MAX_LINE && !line.contains('\n')) {
+        return line;
+    }
+
+    types
+        .iter()
+        .map(|t| format!("\n{}{}| {}", indent, INDENT, t))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!("string", ts_type(&json!({ "type": "string" }), ""));
+        assert_eq!("number", ts_type(&json!({ "type": "integer" }), ""));
+        assert_eq!(
+            "string | null",
+            ts_type(&json!({ "type": ["string", "null"] }), "")
+        );
+        assert_eq!("unknown", ts_type(&json!(true), ""));
+    }
+
+    #[test]
+    fn test_references_and_enums() {
+        assert_eq!(
+            "Ability",
+            ts_type(&json!({ "$ref": "#/definitions/Ability" }), "")
+        );
+        assert_eq!(
+            "\"walk\" | \"fly\"",
+            ts_type(&json!({ "type": "string", "enum": ["walk", "fly"] }), "")
+        );
+        assert_eq!(
+            "Array<Ability>",
+            ts_type(
+                &json!({ "type": "array", "items": { "$ref": "#/definitions/Ability" } }),
+                ""
+            )
+        );
+        assert_eq!(
+            "[Ability, Ability | null]",
+            ts_type(
+                &json!({
+                    "type": "array",
+                    "items": [
+                        { "$ref": "#/definitions/Ability" },
+                        { "anyOf": [{ "$ref": "#/definitions/Ability" }, { "type": "null" }] },
+                    ],
+                }),
+                ""
+            )
+        );
+    }
+
+    #[test]
+    fn test_objects() {
+        let schema = json!({
+            "type": "object",
+            "required": ["id"],
+            "properties": {
+                "id": { "description": "Unique ID", "type": "string" },
+                "hit-dice": { "type": "integer" },
+            },
+            "additionalProperties": true,
+        });
+
+        assert_eq!(
+            "{\n  \"hit-dice\"?: number;\n  /** Unique ID */\n  id: string;\n  [key: string]: unknown;\n}",
+            ts_type(&schema, "")
+        );
+        assert_eq!(
+            "Record<string, number>",
+            ts_type(
+                &json!({ "type": "object", "additionalProperties": { "type": "integer" } }),
+                ""
+            )
+        );
+    }
+
+    #[test]
+    fn test_flattened() {
+        let schema = json!({
+            "type": "object",
+            "oneOf": [{ "$ref": "#/definitions/A" }, { "$ref": "#/definitions/B" }],
+            "properties": { "if": { "$ref": "#/definitions/Condition" } },
+        });
+
+        assert_eq!("{\n  if?: Condition;\n} & (A | B)", ts_type(&schema, ""));
+    }
+
+    #[test]
+    fn test_definitions() {
+        let definitions = definitions().unwrap();
+
+        for name in [
+            "Asset",
+            "Character",
+            "CharacterAssetGrant",
+            "CharacterSheet",
+        ] {
+            assert!(definitions.contains(&format!("export type {} =", name)));
+        }
+    }
+
+    #[test]
+    fn test_generated_file() {
+        let path = ::std::path::Path::new("../../src/models/generated.ts");
+        let definitions = definitions().unwrap();
+
+        if ::std::env::var_os("UPDATE_TYPES").is_some() {
+            ::std::fs::write(path, &definitions).unwrap();
+        }
+
+        assert_eq!(
+            ::std::fs::read_to_string(path).unwrap(),
+            definitions,
+            "src/models/generated.ts is out of date, run `npm run types`"
+        );
+    }
+}
@@ -0,0 +1,25 @@
+//! Writes the TypeScript definitions of the models to the path given, or to
+//! stdout without one.
+use std::env;
+use std::fs;
+use std::process;
+
+fn main() {
+    let definitions = match typescript::definitions() {
+        Ok(definitions) => definitions,
+        Err(e) => {
+            eprintln!("could not generate the definitions: {}", e);
+            process::exit(1);
+        }
+    };
+
+    match env::args().nth(1) {
+        Some(path) => {
+            if let Err(e) = fs::write(&path, definitions) {
+                eprintln!("could not write {}: {}", path, e);
+                process::exit(1);
+            }
+        }
+        None => print!("{}", definitions),
+    }
+}
@@ -0,0 +1,92 @@
+//! Background
+use super::character::{CharacterAssetGrant, Skill};
+use super::condition::{ConditionContext, Conditional};
+use super::{granted, one, Grant, MetaData, Select};
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+
+/// A Background that a Character has
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct Background {
+    /// The Background's metadata
+    pub metadata: MetaData,
+    /// The Background's spec
+    #[serde(default, alias = "specs")]
+    pub spec: BackgroundSpec,
+}
+
+/// Background Specification
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone, JsonSchema)]
+pub struct BackgroundSpec {
+    /// Skills the Background gives Proficiency in
+    #[serde(default)]
+    pub skills: Vec<Skill>,
+    /// Unique IDs of the tools the Background gives Proficiency with
+    #[serde(default)]
+    pub tools: Vec<String>,
+    /// Starting equipment
+    #[serde(default)]
+    pub equipment: Vec<Equipment>,
+    /// Unique ID of the Feature the Background gives
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feature: Option<String>,
+    /// Grants for Background Spec
+    #[serde(default)]
+    pub grant: Vec<Conditional<Grant>>,
+    /// Selects for Background Spec
+    #[serde(default)]
+    pub select: Vec<Select>,
+}
+
+impl BackgroundSpec {
+    /// Returns the [`CharacterAssetGrant`]s this spec gives a Character in `ctx`
+    pub fn grants(&self, ctx: &ConditionContext) -> Vec<CharacterAssetGrant> {
+        self.skills
+            .iter()
+            .map(|skill| skill.proficiency())
+            .chain(self.tools.iter().cloned())
+            .map(|id| CharacterAssetGrant::Proficiency { id })
+            .chain(self.feature.iter().map(|id| CharacterAssetGrant::Feature {
+                id: id.clone(),
+                option: None,
+            }))
+            .chain(granted(&self.grant, ctx))
+            .collect()
+    }
+}
+
+/// An item and how many of it
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct Equipment {
+    /// Unique ID of the item
+    pub id: String,
+    /// How many of the item
+    #[serde(default = "one")]
+    pub quantity: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::Asset;
+
+    #[test]
+    fn test_acolyte_read() {
+        let f = ::std::fs::read_to_string("../../data/background/acolyte.yml").unwrap();
+        let Asset::Background(acolyte) = serde_yaml::from_str(&f).unwrap() else {
+            panic!("expected a background");
+        };
+
+        assert_eq!(vec![Skill::Insight, Skill::Religion], acolyte.spec.skills);
+        assert_eq!(Some(String::from("shelter-of-the-faithful")), acolyte.spec.feature);
+        assert_eq!(6, acolyte.spec.equipment.len());
+        assert_eq!(
+            Equipment {
+                id: String::from("incense"),
+                quantity: 5,
+            },
+            acolyte.spec.equipment[2]
+        );
+        assert_eq!(1, acolyte.spec.equipment[0].quantity);
+    }
+}
@@ -1,10 +1,23 @@
 //! Character
-use super::{Asset, MetaData};
+use super::condition::ConditionContext;
+use super::inventory::Inventory;
+use super::{granted, Asset, MetaData, Movement, Spec, SpeedModifier, Stat};
 use crate::api::fetch_asset_definition;
+use crate::character_sheet::AbilityScores;
+use crate::currency::Currency;
+use crate::library::Library;
+use crate::rules;
+use crate::units::Distance;
+use my_macros::EnumString;
+use serde::{de, Deserializer};
+use schemars::JsonSchema;
 use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Index;
 
 /// The Character Spec that stores all necessary data to build a Character Sheet
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Character {
     /// The Character's metadata
     pub metadata: MetaData,
@@ -47,10 +60,25 @@ impl GetAllGrants for Character {
 
         grants.append(&mut race_grants);
 
+        if let Some(background) = &self.spec.assets.background {
+            grants.extend(background.grants.iter().flat_map(|g| g.all_grants()));
+        }
+
         grants
     }
 }
 
+impl CharacterAssetGrant {
+    /// Returns whether `other` grants the same thing, a Feature being the same
+    /// whichever option was chosen for it
+    pub(crate) fn same_asset(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Feature { id, .. }, Self::Feature { id: other, .. }) => id == other,
+            _ => self == other,
+        }
+    }
+}
+
 impl GetAllGrants for CharacterAssetGrant {
     fn all_grants(&self) -> Vec<CharacterAssetGrant> {
         match self {
@@ -80,23 +108,223 @@ impl GetAllGrants for CharacterAssetGrant {
 }
 
 /// Character Specification
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct CharacterSpec {
     /// The Ability Scores for a Character
     pub abilities: Abilities,
+    /// How the base Ability Scores were generated
+    #[serde(default)]
+    pub generation: AbilityGeneration,
+    /// The Character's experience points
+    #[serde(default)]
+    pub experience: u32,
+    /// Table options that change how the Character is built
+    #[serde(default)]
+    pub options: CharacterOptions,
+    /// Everything the Character is carrying
+    #[serde(default)]
+    pub inventory: Inventory,
+    /// The Character's coins
+    #[serde(default)]
+    pub currency: Currency,
     /// The Character's assets
     #[serde(flatten)]
     pub assets: CharacterAssets,
 }
 
+/// Table options that change how a Character is built and validated
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone, Copy, JsonSchema)]
+pub struct CharacterOptions {
+    /// How the Character gains levels
+    #[serde(default)]
+    pub leveling: Leveling,
+    /// How carried weight slows the Character down
+    #[serde(default)]
+    pub encumbrance: Encumbrance,
+}
+
+/// Which encumbrance rules a Character uses
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Default, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Encumbrance {
+    /// Only the carrying capacity matters
+    #[default]
+    Standard,
+    /// Carrying over 5 and 10 times Strength in pounds slows the Character down
+    Variant,
+}
+
+/// How a Character gains levels
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Default, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Leveling {
+    /// Levels are earned through experience points
+    #[default]
+    Experience,
+    /// Levels are handed out by the DM, experience points are ignored
+    Milestone,
+}
+
 impl Character {
     /// Returns a Character's level
     pub fn level(&self) -> i8 {
         match &self.spec.assets.class {
             CharacterClassSpec::Single(class) => class.level,
-            CharacterClassSpec::Multi(classes) => classes.iter().fold(0, |a, e| a + e.level),
+            CharacterClassSpec::Multi(classes) => {
+                classes.iter().fold(0, |a: i8, e| a.saturating_add(e.level))
+            }
         }
     }
+
+    /// Returns every class the Character has
+    pub fn classes(&self) -> Vec<&CharacterClass> {
+        match &self.spec.assets.class {
+            CharacterClassSpec::Single(class) => vec![class],
+            CharacterClassSpec::Multi(classes) => classes.iter().collect(),
+        }
+    }
+
+    /// Returns every grant of the Character, along with the grants of the Race,
+    /// Background, SubRaces, SubClasses, Traits, Features and Feats it references
+    /// that are defined in `library`
+    pub fn grants(&self, library: &Library) -> Vec<CharacterAssetGrant> {
+        self.expand_grants(self.all_grants(), library)
+    }
+
+    /// Returns every grant of the Character like [`Character::grants`], but as
+    /// if the Feat with the id `feat` was never taken, for checking its prerequisite
+    pub fn grants_without_feat(&self, library: &Library, feat: &str) -> Vec<CharacterAssetGrant> {
+        let mut grants = self.all_grants();
+
+        // Taking the Feat brings in its own grant along with what was chosen for it
+        let taken: Vec<_> = grants
+            .iter()
+            .filter(|g| matches!(g, CharacterAssetGrant::ASI(ASI::Feat { id, .. }) if id == feat))
+            .flat_map(|g| g.all_grants())
+            .collect();
+        for grant in taken {
+            if let Some(i) = grants.iter().position(|g| *g == grant) {
+                grants.remove(i);
+            }
+        }
+
+        self.expand_grants(grants, library)
+    }
+
+    /// Returns `grants` along with the grants of everything they reference
+    /// that is defined in `library`
+    fn expand_grants(
+        &self,
+        mut grants: Vec<CharacterAssetGrant>,
+        library: &Library,
+    ) -> Vec<CharacterAssetGrant> {
+        // Conditions on these grants are checked against the Character without
+        // them, as they could otherwise depend on themselves
+        let ctx = ConditionContext::with_grants(self, &grants);
+
+        // Class grants are checked against the level of their class. Features the
+        // Character lists itself are left out, as it has chosen their options.
+        for class in self.classes() {
+            let Some((_, Spec::Class { grant, .. })) = library.class(&class.id) else {
+                continue;
+            };
+            let more: Vec<_> = granted(grant, &ctx.for_class(&class.id))
+                .filter(|g| !grants.iter().any(|own| own.same_asset(g)))
+                .collect();
+            grants.extend(more);
+        }
+
+        if let Some(race) = library.race(&self.spec.assets.race.id) {
+            grants.extend(race.spec.grants(&ctx));
+        }
+
+        if let Some(background) = self
+            .spec
+            .assets
+            .background
+            .as_ref()
+            .and_then(|b| library.background(&b.id))
+        {
+            grants.extend(background.spec.grants(&ctx));
+        }
+
+        let mut expanded: Vec<(&str, &str, Option<&str>)> = vec![];
+        let mut i = 0;
+        while i < grants.len() {
+            let more = match &grants[i] {
+                CharacterAssetGrant::SubRace { id, .. }
+                    if !expanded.contains(&("sub-race", id, None)) =>
+                {
+                    // A SubRace of another Race brings nothing, and fails validation
+                    library
+                        .sub_race(id)
+                        .filter(|sub_race| sub_race.spec.race == self.spec.assets.race.id)
+                        .map(|sub_race| {
+                            expanded.push(("sub-race", &sub_race.metadata.id, None));
+                            sub_race.spec.spec.grants(&ctx)
+                        })
+                }
+                CharacterAssetGrant::SubClass { id, .. }
+                    if !expanded.contains(&("sub-class", id, None)) =>
+                {
+                    library.sub_class(id).map(|sub_class| {
+                        expanded.push(("sub-class", &sub_class.metadata.id, None));
+                        sub_class.spec.grants(&ctx)
+                    })
+                }
+                CharacterAssetGrant::Trait { id, .. } if !expanded.contains(&("trait", id, None)) => {
+                    library.race_trait(id).map(|t| {
+                        expanded.push(("trait", &t.metadata.id, None));
+                        t.spec.grants(&ctx)
+                    })
+                }
+                CharacterAssetGrant::ASI(ASI::Feat { id, ability, .. }) => {
+                    // A Feat can be taken more than once, each time choosing an ability
+                    library.feat(id).map(|feat| feat.spec.grants(*ability, &ctx))
+                }
+                CharacterAssetGrant::Feature { id, option }
+                    if !expanded.contains(&("feature", id, option.as_deref())) =>
+                {
+                    library.feature(id).map(|feature| {
+                        let option = option.as_deref().and_then(|o| feature.option(o));
+                        expanded.push((
+                            "feature",
+                            &feature.metadata.id,
+                            option.map(|o| o.metadata.id.as_str()),
+                        ));
+
+                        let mut grants = feature.spec.effects.grants(&ctx);
+                        if let Some(option) = option {
+                            grants.extend(option.spec.grants(&ctx));
+                        }
+                        grants
+                    })
+                }
+                _ => None,
+            };
+
+            if let Some(more) = more {
+                grants.extend(more.into_iter().flat_map(|g| g.all_grants()));
+            }
+            i += 1;
+        }
+
+        grants
+    }
+
+    /// Returns the Character's final Ability Scores, after all grants are applied
+    pub fn ability_scores(&self, library: &Library) -> Abilities {
+        AbilityScores::new(self.spec.abilities, &self.grants(library)).totals()
+    }
+
+    /// Returns the experience points still needed to reach the next level,
+    /// or [`None`] if the Character is already at the highest level.
+    pub fn experience_to_next_level(&self) -> Option<u32> {
+        self.level()
+            .checked_add(1)
+            .and_then(rules::experience_for_level)
+            .map(|needed| needed.saturating_sub(self.spec.experience))
+    }
 }
 
 #[cfg(test)]
@@ -124,6 +352,11 @@ mod tests {
                     wisdom: 10,
                     charisma: 13,
                 },
+                generation: Default::default(),
+                experience: 0,
+                options: Default::default(),
+                inventory: Default::default(),
+                currency: Default::default(),
                 assets: character::CharacterAssets {
                     class: CharacterClassSpec::Single(CharacterClass {
                         id: String::from("fighter"),
@@ -131,11 +364,12 @@ mod tests {
                         multiclass: false,
                         grants: vec![
                             CharacterAssetGrant::ASI(ASI::Ability(
-                                String::from("strength"),
-                                Some(String::from("dexterity")),
+                                Ability::Strength,
+                                Some(Ability::Dexterity),
                             )),
                             CharacterAssetGrant::ASI(ASI::Feat {
                                 id: String::from("this feat"),
+                                ability: None,
                                 grants: vec![],
                             }),
                         ],
@@ -144,6 +378,7 @@ mod tests {
                         id: String::from("elf"),
                         grants: vec![],
                     },
+                    background: None,
                 },
             },
         };
@@ -170,6 +405,11 @@ mod tests {
                     wisdom: 10,
                     charisma: 13,
                 },
+                generation: Default::default(),
+                experience: 0,
+                options: Default::default(),
+                inventory: Default::default(),
+                currency: Default::default(),
                 assets: character::CharacterAssets {
                     class: CharacterClassSpec::Multi(vec![
                         CharacterClass {
@@ -178,11 +418,12 @@ mod tests {
                             multiclass: false,
                             grants: vec![
                                 CharacterAssetGrant::ASI(ASI::Ability(
-                                    String::from("strength"),
-                                    Some(String::from("dexterity")),
+                                    Ability::Strength,
+                                    Some(Ability::Dexterity),
                                 )),
                                 CharacterAssetGrant::ASI(ASI::Feat {
                                     id: String::from("this feat"),
+                                    ability: None,
                                     grants: vec![],
                                 }),
                             ],
@@ -193,11 +434,12 @@ mod tests {
                             multiclass: false,
                             grants: vec![
                                 CharacterAssetGrant::ASI(ASI::Ability(
-                                    String::from("strength"),
-                                    Some(String::from("dexterity")),
+                                    Ability::Strength,
+                                    Some(Ability::Dexterity),
                                 )),
                                 CharacterAssetGrant::ASI(ASI::Feat {
                                     id: String::from("this feat"),
+                                    ability: None,
                                     grants: vec![],
                                 }),
                             ],
@@ -207,25 +449,114 @@ mod tests {
                         id: String::from("elf"),
                         grants: vec![],
                     },
+                    background: None,
                 },
             },
         };
 
         assert_eq!(3, ch.level());
     }
+
+    #[test]
+    fn test_character_experience_to_next_level() {
+        let mut ch: Character = serde_yaml::from_str(
+            "
+metadata:
+  id: uuid-lmao-lol
+  name: foobar
+spec:
+  abilities:
+    strength: 9
+    dexterity: 20
+    constitution: 11
+    intelligence: 11
+    wisdom: 10
+    charisma: 13
+  experience: 1000
+  class:
+    id: fighter
+    level: 3
+    grants: []
+  race:
+    id: elf
+    grants: []
+",
+        )
+        .unwrap();
+
+        assert_eq!(Some(1_700), ch.experience_to_next_level());
+
+        ch.spec.experience = 3_000;
+        assert_eq!(Some(0), ch.experience_to_next_level());
+
+        if let CharacterClassSpec::Single(class) = &mut ch.spec.assets.class {
+            class.level = 20;
+        }
+        assert_eq!(None, ch.experience_to_next_level());
+
+        if let CharacterClassSpec::Single(class) = &mut ch.spec.assets.class {
+            class.level = 127;
+        }
+        assert_eq!(None, ch.experience_to_next_level());
+    }
+
+    #[test]
+    fn test_skill_names() {
+        for skill in Skill::ALL {
+            assert_eq!(
+                skill,
+                serde_yaml::from_str::<Skill>(skill.name()).unwrap(),
+                "{}",
+                skill
+            );
+        }
+        assert_eq!("skill-sleight-of-hand", Skill::SleightOfHand.proficiency());
+        assert_eq!(Ability::Wisdom, Skill::Perception.ability());
+    }
+
+    #[test]
+    fn test_ability_from_str() {
+        assert_eq!(Ok(Ability::Wisdom), Ability::from_str("wisdom"));
+        assert_eq!(Err(()), Ability::from_str("Wisdom"));
+        assert_eq!(
+            Ability::ALL.to_vec(),
+            serde_yaml::from_str::<Vec<Ability>>(
+                "[strength, dexterity, constitution, intelligence, wisdom, charisma]"
+            )
+            .unwrap()
+        );
+        assert_eq!("charisma", Ability::Charisma.to_string());
+    }
+
+    #[test]
+    fn test_misspelled_ability_is_rejected() {
+        let asi = serde_yaml::from_str::<CharacterAssetGrant>("type: asi\nability: [strenght, ~]");
+        let score =
+            serde_yaml::from_str::<CharacterAssetGrant>("type: abilityscore\nid: wisdum\nadd: 1");
+
+        assert!(asi.is_err());
+        assert!(score.is_err());
+        assert_eq!(
+            CharacterAssetGrant::ASI(ASI::Ability(Ability::Strength, None)),
+            serde_yaml::from_str("type: asi\nability: [strength, ~]").unwrap()
+        );
+    }
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 /// CharacterAssets
 pub struct CharacterAssets {
     /// CharacterClassSpec
     pub class: CharacterClassSpec,
     /// CharacterRace
     pub race: CharacterRace,
+    /// CharacterBackground
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<CharacterBackground>,
 }
 
 /// CharacterClassSpec
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(untagged)]
 pub enum CharacterClassSpec {
     /// For Multi-Classed Characters
@@ -235,7 +566,7 @@ pub enum CharacterClassSpec {
 }
 
 /// A Character's Class
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct CharacterClass {
     /// Unique ID
     pub id: String,
@@ -249,7 +580,7 @@ pub struct CharacterClass {
 }
 
 /// A Character's Race
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct CharacterRace {
     /// Unique ID
     pub id: String,
@@ -257,8 +588,17 @@ pub struct CharacterRace {
     pub grants: Vec<CharacterAssetGrant>,
 }
 
+/// A Character's Background
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct CharacterBackground {
+    /// Unique ID
+    pub id: String,
+    /// Assets that are granted to the Character by this background
+    pub grants: Vec<CharacterAssetGrant>,
+}
+
 /// Assets
-#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(rename_all = "lowercase", tag = "type")]
 pub enum CharacterAssetGrant {
     // This seems like a bad way to go about this lmao
@@ -276,6 +616,9 @@ pub enum CharacterAssetGrant {
     Feature {
         /// Unique ID
         id: String,
+        /// Unique ID of the option chosen, for Features that have options
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        option: Option<String>,
     },
     /// Grants a Spell to the Character
     Spell {
@@ -287,6 +630,22 @@ pub enum CharacterAssetGrant {
         /// Unique ID
         id: String,
     },
+    /// Grants a Vision, or any other sense, to the Character
+    Vision {
+        /// Unique ID
+        id: String,
+        /// How far the sense reaches
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        range: Option<Distance>,
+    },
+    /// Grants a speed, or a change to one, to the Character
+    Speed {
+        /// The kind of movement
+        id: Movement,
+        /// How the speed changes
+        #[serde(flatten)]
+        modifier: SpeedModifier,
+    },
     /// Grants a Trait to the Character
     Trait {
         /// Unique ID
@@ -325,10 +684,25 @@ pub enum CharacterAssetGrant {
     /// Adds a number to an AbilityScore
     AbilityScore {
         /// ID of the AbilityScore to modify
-        id: String,
+        id: Ability,
         /// Number to add to the AbilityScore
         add: i8,
     },
+    /// Adds to a stat on the Character sheet
+    Stat {
+        /// Path of the stat
+        id: String,
+        /// Value to add to the stat
+        value: Stat,
+    },
+    /// Raises the highest value an AbilityScore can be increased to
+    #[serde(rename = "ability-max")]
+    AbilityMax {
+        /// ID of the AbilityScore to raise the maximum of
+        id: Ability,
+        /// The new maximum
+        max: i8,
+    },
 }
 
 /// Trait to fetch the Asset definition from the api
@@ -350,22 +724,231 @@ impl FetchDefFromAPI<Asset> for CharacterAssetGrant {
 }
 
 /// Ability Score Improvement
-#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ASI {
     /// Ability Scores to Improve
-    Ability(String, Option<String>),
+    Ability(Ability, Option<Ability>),
     /// Feat to get if not taking Ability Score Improvement
     Feat {
         /// Feat ID
         id: String,
-        /// Assets granted to the Character by this Feat
+        /// Ability increased by the Feat, for Feats that increase one
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ability: Option<Ability>,
+        /// Assets chosen for the Feat's selects
+        #[serde(default)]
         grants: Vec<CharacterAssetGrant>,
     },
 }
 
+/// One of the six Abilities
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Clone, Copy, EnumString, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Ability {
+    /// Strength
+    Strength,
+    /// Dexterity
+    Dexterity,
+    /// Constitution
+    Constitution,
+    /// Intelligence
+    Intelligence,
+    /// Wisdom
+    Wisdom,
+    /// Charisma
+    Charisma,
+}
+
+const ABILITY_NAMES: [&str; 6] = [
+    "strength",
+    "dexterity",
+    "constitution",
+    "intelligence",
+    "wisdom",
+    "charisma",
+];
+
+impl Ability {
+    /// Every Ability, in the order they appear on a Character Sheet
+    pub const ALL: [Ability; 6] = [
+        Ability::Strength,
+        Ability::Dexterity,
+        Ability::Constitution,
+        Ability::Intelligence,
+        Ability::Wisdom,
+        Ability::Charisma,
+    ];
+
+    /// Returns the name of the Ability, as written in assets
+    pub fn name(&self) -> &'static str {
+        ABILITY_NAMES[*self as usize]
+    }
+
+    /// Returns the Unique ID of the Proficiency in saving throws with this Ability
+    pub fn proficiency(&self) -> String {
+        format!("saving-throw-{}", self.name())
+    }
+}
+
+impl fmt::Display for Ability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Ability {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+
+        Ability::from_str(&name).map_err(|_| de::Error::unknown_variant(&name, &ABILITY_NAMES))
+    }
+}
+
+/// One of the Skills, each of which is checked with an [`Ability`]
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Skill {
+    /// Acrobatics
+    Acrobatics,
+    /// Animal Handling
+    AnimalHandling,
+    /// Arcana
+    Arcana,
+    /// Athletics
+    Athletics,
+    /// Deception
+    Deception,
+    /// History
+    History,
+    /// Insight
+    Insight,
+    /// Intimidation
+    Intimidation,
+    /// Investigation
+    Investigation,
+    /// Medicine
+    Medicine,
+    /// Nature
+    Nature,
+    /// Perception
+    Perception,
+    /// Performance
+    Performance,
+    /// Persuasion
+    Persuasion,
+    /// Religion
+    Religion,
+    /// Sleight of Hand
+    SleightOfHand,
+    /// Stealth
+    Stealth,
+    /// Survival
+    Survival,
+}
+
+const SKILL_NAMES: [&str; 18] = [
+    "acrobatics",
+    "animal-handling",
+    "arcana",
+    "athletics",
+    "deception",
+    "history",
+    "insight",
+    "intimidation",
+    "investigation",
+    "medicine",
+    "nature",
+    "perception",
+    "performance",
+    "persuasion",
+    "religion",
+    "sleight-of-hand",
+    "stealth",
+    "survival",
+];
+
+impl Skill {
+    /// Every Skill, in alphabetical order
+    pub const ALL: [Skill; 18] = [
+        Skill::Acrobatics,
+        Skill::AnimalHandling,
+        Skill::Arcana,
+        Skill::Athletics,
+        Skill::Deception,
+        Skill::History,
+        Skill::Insight,
+        Skill::Intimidation,
+        Skill::Investigation,
+        Skill::Medicine,
+        Skill::Nature,
+        Skill::Perception,
+        Skill::Performance,
+        Skill::Persuasion,
+        Skill::Religion,
+        Skill::SleightOfHand,
+        Skill::Stealth,
+        Skill::Survival,
+    ];
+
+    /// Returns the name of the Skill, as written in assets
+    pub fn name(&self) -> &'static str {
+        SKILL_NAMES[*self as usize]
+    }
+
+    /// Returns the Ability the Skill is checked with
+    pub fn ability(&self) -> Ability {
+        match self {
+            Skill::Athletics => Ability::Strength,
+            Skill::Acrobatics | Skill::SleightOfHand | Skill::Stealth => Ability::Dexterity,
+            Skill::Arcana
+            | Skill::History
+            | Skill::Investigation
+            | Skill::Nature
+            | Skill::Religion => Ability::Intelligence,
+            Skill::AnimalHandling
+            | Skill::Insight
+            | Skill::Medicine
+            | Skill::Perception
+            | Skill::Survival => Ability::Wisdom,
+            Skill::Deception | Skill::Intimidation | Skill::Performance | Skill::Persuasion => {
+                Ability::Charisma
+            }
+        }
+    }
+
+    /// Returns the Unique ID of the Proficiency in this Skill
+    pub fn proficiency(&self) -> String {
+        format!("skill-{}", self.name())
+    }
+}
+
+impl fmt::Display for Skill {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// How a Character's base Ability Scores were generated
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone, JsonSchema)]
+#[serde(rename_all = "kebab-case", tag = "method")]
+pub enum AbilityGeneration {
+    /// Bought with points from the point-buy cost table
+    PointBuy,
+    /// Assigned from the standard array
+    StandardArray,
+    /// Rolled with 4d6, dropping the lowest die
+    Rolled {
+        /// The four dice rolled for each Ability
+        rolls: BTreeMap<Ability, [u8; 4]>,
+    },
+    /// Entered by hand, only checked against the absolute limits
+    #[default]
+    Manual,
+}
+
 /// A Character's Ability Scores
-#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Copy)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Copy, JsonSchema)]
 pub struct Abilities {
     /// Strength
     pub strength: i8,
@@ -380,3 +963,18 @@ pub struct Abilities {
     /// Charisma
     pub charisma: i8,
 }
+
+impl Index<Ability> for Abilities {
+    type Output = i8;
+
+    fn index(&self, ability: Ability) -> &Self::Output {
+        match ability {
+            Ability::Strength => &self.strength,
+            Ability::Dexterity => &self.dexterity,
+            Ability::Constitution => &self.constitution,
+            Ability::Intelligence => &self.intelligence,
+            Ability::Wisdom => &self.wisdom,
+            Ability::Charisma => &self.charisma,
+        }
+    }
+}
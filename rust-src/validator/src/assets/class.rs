@@ -1 +1,103 @@
 //! Class
+use super::character::CharacterAssetGrant;
+use super::condition::{ConditionContext, Conditional};
+use super::{granted, Grant, MetaData, Select};
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+
+/// A SubClass of a Class, such as a Fighter's Martial Archetype
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct SubClass {
+    /// The SubClass's metadata
+    pub metadata: MetaData,
+    /// The SubClass's spec
+    #[serde(alias = "specs")]
+    pub spec: SubClassSpec,
+}
+
+/// SubClass Specification
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct SubClassSpec {
+    /// Unique ID of the Class this is a SubClass of
+    pub class: String,
+    /// Grants for SubClass Spec, with levels being levels of the Class
+    #[serde(default)]
+    pub grant: Vec<Conditional<Grant>>,
+    /// Selects for SubClass Spec
+    #[serde(default)]
+    pub select: Vec<Select>,
+}
+
+impl SubClassSpec {
+    /// Returns the [`CharacterAssetGrant`]s this spec gives a Character in `ctx`,
+    /// checking `level:` conditions against the level of its Class
+    pub fn grants(&self, ctx: &ConditionContext) -> Vec<CharacterAssetGrant> {
+        granted(&self.grant, &ctx.for_class(&self.class)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::character::Abilities;
+    use crate::assets::condition::ClassLevel;
+
+    #[test]
+    fn test_sub_class_grants_use_class_level() {
+        let champion: SubClass = serde_yaml::from_str(
+            "
+metadata:
+  id: champion
+  name: Champion
+spec:
+  class: fighter
+  grant:
+    - type: feature
+      id: improved-critical
+      if:
+        level: 3
+    - type: feature
+      id: remarkable-athlete
+      if:
+        level: 7
+",
+        )
+        .unwrap();
+
+        let ctx = |fighter: i8| ConditionContext {
+            abilities: Abilities {
+                strength: 10,
+                dexterity: 10,
+                constitution: 10,
+                intelligence: 10,
+                wisdom: 10,
+                charisma: 10,
+            },
+            classes: vec![
+                ClassLevel {
+                    id: String::from("fighter"),
+                    level: fighter,
+                    multiclass: false,
+                },
+                ClassLevel {
+                    id: String::from("wizard"),
+                    level: 4,
+                    multiclass: true,
+                },
+            ],
+            class: None,
+            proficiencies: vec![],
+            races: vec![],
+            spellcasting: false,
+        };
+
+        assert_eq!(
+            vec![CharacterAssetGrant::Feature {
+                id: String::from("improved-critical"),
+                option: None,
+            }],
+            champion.spec.grants(&ctx(3))
+        );
+        assert_eq!(2, champion.spec.grants(&ctx(7)).len());
+    }
+}
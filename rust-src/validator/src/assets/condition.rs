@@ -0,0 +1,351 @@
+//! Conditions, the `if:` field that gates when part of an Asset applies to a Character
+use super::character::{Abilities, Ability, Character, CharacterAssetGrant};
+use crate::character_sheet::AbilityScores;
+use crate::library::Library;
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Either a single value or a list of values
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(untagged)]
+pub enum OneOrMany<T> {
+    /// A single value
+    One(T),
+    /// A list of values
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    /// Iterates over every value
+    pub fn iter(&self) -> ::std::slice::Iter<'_, T> {
+        match self {
+            Self::One(value) => ::std::slice::from_ref(value).iter(),
+            Self::Many(values) => values.iter(),
+        }
+    }
+}
+
+/// Minimum Ability Scores
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(untagged)]
+pub enum AbilityRequirement {
+    /// Every listed ability must meet its minimum
+    All(HashMap<Ability, i8>),
+    /// At least one of the listed requirements must be met
+    Any(Vec<HashMap<Ability, i8>>),
+}
+
+impl AbilityRequirement {
+    fn is_met(&self, abilities: &Abilities) -> bool {
+        let all_met = |minimums: &HashMap<Ability, i8>| {
+            minimums
+                .iter()
+                .all(|(ability, min)| abilities[*ability] >= *min)
+        };
+
+        match self {
+            Self::All(minimums) => all_met(minimums),
+            Self::Any(options) => options.iter().any(all_met),
+        }
+    }
+}
+
+/// A Condition that must hold for something to apply to a Character.
+///
+/// Every field that is set must hold for the Condition to be met.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct Condition {
+    /// Minimum Ability Scores, these are always minimum requirements
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ability: Option<AbilityRequirement>,
+    /// Levels of the class this rule is in, met once any of them is reached
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<OneOrMany<i8>>,
+    /// Classes, at least one of which the Character must have
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<OneOrMany<String>>,
+    /// Classes, at least one of which the Character must have multi-classed into
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multiclass: Option<OneOrMany<String>>,
+    /// Proficiencies, at least one of which the Character must have
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proficiency: Option<OneOrMany<String>>,
+    /// Races or SubRaces, one of which the Character must be
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub race: Option<OneOrMany<String>>,
+    /// Whether the Character must be able to cast at least one spell
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spellcasting: Option<bool>,
+    /// A Condition that must not hold
+    #[serde(rename = "$not", default, skip_serializing_if = "Option::is_none")]
+    pub not: Option<Box<Condition>>,
+}
+
+impl Condition {
+    /// Returns whether this Condition holds in `ctx`
+    pub fn is_met(&self, ctx: &ConditionContext) -> bool {
+        self.occurrences(ctx) > 0
+    }
+
+    /// Returns how many times this Condition holds in `ctx`.
+    ///
+    /// This is the number of listed levels that have been reached, so a grant
+    /// with `level: [4, 8]` applies twice to a level 8 class.
+    pub fn occurrences(&self, ctx: &ConditionContext) -> usize {
+        let ability = self
+            .ability
+            .as_ref()
+            .is_none_or(|req| req.is_met(&ctx.abilities));
+
+        let class = self
+            .class
+            .as_ref()
+            .is_none_or(|ids| ids.iter().any(|id| ctx.class_level(id).is_some()));
+
+        let multiclass = self.multiclass.as_ref().is_none_or(|ids| {
+            ids.iter()
+                .any(|id| ctx.classes.iter().any(|c| &c.id == id && c.multiclass))
+        });
+
+        let proficiency = self
+            .proficiency
+            .as_ref()
+            .is_none_or(|ids| ids.iter().any(|id| ctx.proficiencies.contains(id)));
+
+        let race = self
+            .race
+            .as_ref()
+            .is_none_or(|ids| ids.iter().any(|id| ctx.races.contains(id)));
+
+        let spellcasting = self
+            .spellcasting
+            .is_none_or(|spellcasting| spellcasting == ctx.spellcasting);
+
+        let not = self.not.as_ref().is_none_or(|cond| !cond.is_met(ctx));
+
+        if !(ability && class && multiclass && proficiency && race && spellcasting && not) {
+            return 0;
+        }
+
+        match &self.level {
+            Some(levels) => levels.iter().filter(|&&l| l <= ctx.level()).count(),
+            None => 1,
+        }
+    }
+}
+
+/// A value that only applies to a Character when its Condition holds
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct Conditional<T> {
+    /// The value
+    #[serde(flatten)]
+    pub value: T,
+    /// The Condition gating the value
+    #[serde(rename = "if", default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<Condition>,
+}
+
+impl<T> Conditional<T> {
+    /// Returns how many times the value applies in `ctx`
+    pub fn occurrences(&self, ctx: &ConditionContext) -> usize {
+        self.condition.as_ref().map_or(1, |c| c.occurrences(ctx))
+    }
+
+    /// Returns whether the value applies in `ctx`
+    pub fn is_met(&self, ctx: &ConditionContext) -> bool {
+        self.occurrences(ctx) > 0
+    }
+}
+
+impl<T> From<T> for Conditional<T> {
+    fn from(value: T) -> Self {
+        Self {
+            value,
+            condition: None,
+        }
+    }
+}
+
+/// A class a Character has, as seen by a [`Condition`]
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClassLevel {
+    /// Unique ID of the class
+    pub id: String,
+    /// The level of the class
+    pub level: i8,
+    /// If this class was chosen as a multi-class
+    pub multiclass: bool,
+}
+
+/// Everything about a Character that a [`Condition`] can check
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConditionContext {
+    /// The Character's final Ability Scores
+    pub abilities: Abilities,
+    /// The Character's classes
+    pub classes: Vec<ClassLevel>,
+    /// The class that the rule being checked is in, if any
+    pub class: Option<String>,
+    /// Unique IDs of the Character's Proficiencies
+    pub proficiencies: Vec<String>,
+    /// Unique IDs of the Character's Race and SubRaces
+    pub races: Vec<String>,
+    /// Whether the Character can cast at least one spell
+    pub spellcasting: bool,
+}
+
+impl ConditionContext {
+    /// Returns a context for checking rules on `character`, whose Assets are defined in `library`
+    pub fn new(character: &Character, library: &Library) -> Self {
+        Self::with_grants(character, &character.grants(library))
+    }
+
+    /// Returns a context for checking rules on `character`, as if `grants` were all of its grants
+    pub fn with_grants(character: &Character, grants: &[CharacterAssetGrant]) -> Self {
+        Self {
+            abilities: AbilityScores::new(character.spec.abilities, grants).totals(),
+            classes: character
+                .classes()
+                .into_iter()
+                .map(|c| ClassLevel {
+                    id: c.id.clone(),
+                    level: c.level,
+                    multiclass: c.multiclass,
+                })
+                .collect(),
+            class: None,
+            proficiencies: grants
+                .iter()
+                .filter_map(|grant| match grant {
+                    CharacterAssetGrant::Proficiency { id } => Some(id.clone()),
+                    _ => None,
+                })
+                .collect(),
+            races: ::std::iter::once(character.spec.assets.race.id.clone())
+                .chain(grants.iter().filter_map(|grant| match grant {
+                    CharacterAssetGrant::SubRace { id, .. } => Some(id.clone()),
+                    _ => None,
+                }))
+                .collect(),
+            spellcasting: grants
+                .iter()
+                .any(|grant| matches!(grant, CharacterAssetGrant::Spell { .. })),
+        }
+    }
+
+    /// Returns this context for checking the rules of the class `id`
+    pub fn for_class(&self, id: &str) -> Self {
+        Self {
+            class: Some(id.to_string()),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the level of the class `id`, if the Character has it
+    pub fn class_level(&self, id: &str) -> Option<i8> {
+        self.classes.iter().find(|c| c.id == id).map(|c| c.level)
+    }
+
+    /// Returns the level that `level:` conditions are checked against, being
+    /// the level of the current class, or the Character's level outside of a class.
+    pub fn level(&self) -> i8 {
+        match &self.class {
+            Some(id) => self.class_level(id).unwrap_or(0),
+            None => self.classes.iter().map(|c| c.level).sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct MockChar {
+        abilities: HashMap<Ability, i8>,
+        level: i8,
+    }
+
+    #[derive(Deserialize)]
+    struct Mock {
+        char: MockChar,
+        #[serde(rename = "if")]
+        condition: Condition,
+        result: bool,
+    }
+
+    fn context(abilities: &HashMap<Ability, i8>, classes: Vec<ClassLevel>) -> ConditionContext {
+        let score = |ability| *abilities.get(&ability).unwrap_or(&10);
+
+        ConditionContext {
+            abilities: Abilities {
+                strength: score(Ability::Strength),
+                dexterity: score(Ability::Dexterity),
+                constitution: score(Ability::Constitution),
+                intelligence: score(Ability::Intelligence),
+                wisdom: score(Ability::Wisdom),
+                charisma: score(Ability::Charisma),
+            },
+            classes,
+            class: None,
+            proficiencies: vec![String::from("armor-medium")],
+            races: vec![String::from("elf"), String::from("high-elf")],
+            spellcasting: false,
+        }
+    }
+
+    fn fighter(level: i8, multiclass: bool) -> ClassLevel {
+        ClassLevel {
+            id: String::from("fighter"),
+            level,
+            multiclass,
+        }
+    }
+
+    #[test]
+    fn test_mock_conditions() {
+        let f = ::std::fs::read_to_string("mocks.yml").unwrap();
+
+        for doc in serde_yaml::Deserializer::from_str(&f) {
+            let mock = Mock::deserialize(doc).unwrap();
+            let ctx = context(&mock.char.abilities, vec![fighter(mock.char.level, false)]);
+
+            assert_eq!(mock.result, mock.condition.is_met(&ctx), "{:?}", mock.condition);
+        }
+    }
+
+    #[test]
+    fn test_level_occurrences() {
+        let cond: Condition = serde_yaml::from_str("level: [4, 6, 8, 12]").unwrap();
+        let ctx = context(&HashMap::new(), vec![fighter(8, false)]).for_class("fighter");
+
+        assert_eq!(3, cond.occurrences(&ctx));
+        assert_eq!(0, cond.occurrences(&ctx.for_class("wizard")));
+    }
+
+    #[test]
+    fn test_proficiency_race_and_spellcasting() {
+        let ctx = context(&HashMap::new(), vec![fighter(1, false)]);
+        let met = |yaml: &str| serde_yaml::from_str::<Condition>(yaml).unwrap().is_met(&ctx);
+
+        assert!(met("proficiency: [armor-heavy, armor-medium]"));
+        assert!(!met("proficiency: armor-heavy"));
+        assert!(met("race: high-elf"));
+        assert!(!met("race: [dwarf, halfling]"));
+        assert!(met("spellcasting: false"));
+        assert!(!met("spellcasting: true"));
+    }
+
+    #[test]
+    fn test_class_and_not_multiclass() {
+        let cond: Condition =
+            serde_yaml::from_str("class: [fighter, paladin]\n$not:\n  multiclass: fighter")
+                .unwrap();
+
+        assert!(cond.is_met(&context(&HashMap::new(), vec![fighter(1, false)])));
+        assert!(!cond.is_met(&context(&HashMap::new(), vec![fighter(1, true)])));
+        assert!(!cond.is_met(&context(&HashMap::new(), vec![])));
+    }
+}
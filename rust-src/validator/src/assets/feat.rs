@@ -0,0 +1,92 @@
+//! Feat
+use super::character::{Ability, CharacterAssetGrant};
+use super::condition::{Condition, ConditionContext, Conditional, OneOrMany};
+use super::{granted, Grant, MetaData, Select};
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+
+/// A Feat that a Character can take instead of an Ability Score Improvement
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct Feat {
+    /// The Feat's metadata
+    pub metadata: MetaData,
+    /// The Feat's spec
+    #[serde(default)]
+    pub spec: FeatSpec,
+}
+
+/// Feat Specification
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone, JsonSchema)]
+pub struct FeatSpec {
+    /// What a Character must meet to take this Feat
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prerequisite: Option<Condition>,
+    /// Abilities, one of which the Character increases by 1 when taking this Feat
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ability: Option<OneOrMany<Ability>>,
+    /// Grants for Feat Spec
+    #[serde(default)]
+    pub grant: Vec<Conditional<Grant>>,
+    /// Selects for Feat Spec
+    #[serde(default)]
+    pub select: Vec<Select>,
+}
+
+impl FeatSpec {
+    /// Returns whether taking this Feat can increase `ability`
+    pub fn can_increase(&self, ability: Ability) -> bool {
+        self.ability
+            .as_ref()
+            .is_some_and(|abilities| abilities.iter().any(|&a| a == ability))
+    }
+
+    /// Returns the [`CharacterAssetGrant`]s this spec gives a Character in `ctx`
+    /// that increased `ability` with it
+    pub fn grants(
+        &self,
+        ability: Option<Ability>,
+        ctx: &ConditionContext,
+    ) -> Vec<CharacterAssetGrant> {
+        ability
+            .filter(|ability| self.can_increase(*ability))
+            .map(|id| CharacterAssetGrant::AbilityScore { id, add: 1 })
+            .into_iter()
+            .chain(granted(&self.grant, ctx))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_half_feat_read() {
+        let feat: Feat = serde_yaml::from_str(
+            "
+metadata:
+  id: heavily-armored
+  name: Heavily Armored
+spec:
+  prerequisite:
+    proficiency: armor-medium
+  ability: strength
+  grant:
+    - type: proficiency
+      id: armor-heavy
+",
+        )
+        .unwrap();
+
+        assert!(feat.spec.can_increase(Ability::Strength));
+        assert!(!feat.spec.can_increase(Ability::Dexterity));
+        assert_eq!(
+            Some(vec![String::from("armor-medium")]),
+            feat.spec
+                .prerequisite
+                .and_then(|p| p.proficiency)
+                .map(|ids| ids.iter().cloned().collect())
+        );
+        assert_eq!(1, feat.spec.grant.len());
+    }
+}
@@ -0,0 +1,124 @@
+//! Feature
+use std::collections::BTreeMap;
+
+use super::character::CharacterAssetGrant;
+use super::condition::{Condition, ConditionContext, Conditional};
+use super::{granted, Grant, MetaData, Stat};
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+
+/// A Feature, granted by a class, race or anything else
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct Feature {
+    /// The Feature's metadata
+    pub metadata: MetaData,
+    /// The Feature's spec
+    #[serde(default, alias = "specs")]
+    pub spec: FeatureSpec,
+}
+
+impl Feature {
+    /// Returns the option with the id `id`
+    pub fn option(&self, id: &str) -> Option<&FeatureOption> {
+        self.spec.options.iter().find(|o| o.metadata.id == id)
+    }
+}
+
+/// Feature Specification
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone, JsonSchema)]
+pub struct FeatureSpec {
+    /// Options, one of which must be chosen if there are any
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<FeatureOption>,
+    /// Grants and stats every Character with this Feature gets
+    #[serde(flatten)]
+    pub effects: FeatureEffects,
+}
+
+/// An option of a [`Feature`]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct FeatureOption {
+    /// The option's metadata
+    pub metadata: MetaData,
+    /// What a Character must meet to choose this option
+    #[serde(rename = "if", default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<Condition>,
+    /// Grants and stats a Character that chose this option gets
+    #[serde(default, alias = "specs")]
+    pub spec: FeatureEffects,
+}
+
+/// What a Feature, or an option of one, does to a Character
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone, JsonSchema)]
+pub struct FeatureEffects {
+    /// Grants
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub grant: Vec<Conditional<Grant>>,
+    /// Stats, by their path on the sheet
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub stat: BTreeMap<String, Stat>,
+}
+
+impl FeatureEffects {
+    /// Returns the [`CharacterAssetGrant`]s this gives a Character in `ctx`
+    pub fn grants(&self, ctx: &ConditionContext) -> Vec<CharacterAssetGrant> {
+        granted(&self.grant, ctx)
+            .chain(self.stat.iter().map(|(id, &value)| CharacterAssetGrant::Stat {
+                id: id.clone(),
+                value,
+            }))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIGHTING_STYLE: &str = "
+metadata:
+  id: fighting-style
+  name: Fighting Style
+spec:
+  options:
+    - metadata:
+        id: archery
+        name: Archery
+      if:
+        class:
+          - fighter
+          - ranger
+      spec:
+        stat:
+          ranged.attack: 2
+    - metadata:
+        id: defense
+        name: Defense
+      spec:
+        stat:
+          armored.ac: 1
+";
+
+    #[test]
+    fn test_feature_options_read() {
+        let feature: Feature = serde_yaml::from_str(FIGHTING_STYLE).unwrap();
+        let archery = feature.option("archery").unwrap();
+
+        assert_eq!(2, feature.spec.options.len());
+        assert_eq!(
+            Some(&Stat::Number(2)),
+            archery.spec.stat.get("ranged.attack")
+        );
+        assert!(archery.condition.is_some());
+        assert!(feature.option("defense").unwrap().condition.is_none());
+        assert!(feature.option("dueling").is_none());
+    }
+
+    #[test]
+    fn test_feature_without_spec_read() {
+        let feature: Feature =
+            serde_yaml::from_str("metadata:\n  id: second-wind\n  name: Second Wind").unwrap();
+
+        assert_eq!(FeatureSpec::default(), feature.spec);
+    }
+}
@@ -0,0 +1,188 @@
+//! A Character's Inventory
+use super::item::{Item, ItemKind};
+use super::one;
+use crate::library::Library;
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+
+/// Everything a Character is carrying
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone, JsonSchema)]
+pub struct Inventory {
+    /// Items carried, including containers and what is in them
+    #[serde(default)]
+    pub items: Vec<InventoryItem>,
+}
+
+impl Inventory {
+    /// Returns every Item carried, including Items inside of containers
+    pub fn all_items(&self) -> Vec<&InventoryItem> {
+        let mut all = vec![];
+        flatten(&self.items, &mut all);
+        all
+    }
+
+    /// Returns every Item inside of a container
+    pub fn stowed_items(&self) -> Vec<&InventoryItem> {
+        let mut stowed = vec![];
+        for item in &self.items {
+            flatten(&item.contents, &mut stowed);
+        }
+        stowed
+    }
+
+    /// Returns the total weight of every Item carried, in pounds.
+    ///
+    /// Items that aren't in `library` weigh nothing.
+    pub fn weight(&self, library: &Library) -> f32 {
+        self.all_items()
+            .iter()
+            .filter_map(|item| {
+                library
+                    .item(&item.id)
+                    .map(|def| def.spec.weight * item.quantity as f32)
+            })
+            .fold(0.0, |total, weight| total + weight)
+    }
+
+    /// Returns every equipped Item, along with its definition in `library`.
+    ///
+    /// Items inside of containers are never equipped, see [`Inventory::stowed_items`].
+    pub fn equipped<'a>(&'a self, library: &'a Library) -> Vec<(&'a InventoryItem, &'a Item)> {
+        self.items
+            .iter()
+            .filter(|item| item.equipped)
+            .filter_map(|item| library.item(&item.id).map(|def| (item, def)))
+            .collect()
+    }
+}
+
+/// Adds every Item in `items`, and in their contents, to `all`
+fn flatten<'a>(items: &'a [InventoryItem], all: &mut Vec<&'a InventoryItem>) {
+    for item in items {
+        all.push(item);
+        flatten(&item.contents, all);
+    }
+}
+
+/// An Item in an [`Inventory`]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct InventoryItem {
+    /// Unique ID of the Item
+    pub id: String,
+    /// How many of the Item
+    #[serde(default = "one")]
+    pub quantity: u32,
+    /// Whether the Item is worn or wielded, which Items inside of containers can't be
+    #[serde(default)]
+    pub equipped: bool,
+    /// Whether the Character is attuned to the Item, which Items inside of containers can't be
+    #[serde(default)]
+    pub attuned: bool,
+    /// Items inside of this one, for containers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub contents: Vec<InventoryItem>,
+}
+
+impl InventoryItem {
+    /// Returns whether the Item can hold other Items, according to `library`
+    pub fn is_container(&self, library: &Library) -> bool {
+        library
+            .item(&self.id)
+            .is_some_and(|item| matches!(item.spec.kind, ItemKind::Container { .. }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inventory_all_items() {
+        let inventory: Inventory = serde_yaml::from_str(
+            "
+items:
+  - id: longsword
+    equipped: true
+  - id: backpack
+    contents:
+      - id: rations
+        quantity: 5
+      - id: pouch
+        contents:
+          - id: ring-of-protection
+            attuned: true
+",
+        )
+        .unwrap();
+
+        let ids: Vec<&str> = inventory
+            .all_items()
+            .iter()
+            .map(|item| item.id.as_str())
+            .collect();
+
+        assert_eq!(
+            vec!["longsword", "backpack", "rations", "pouch", "ring-of-protection"],
+            ids
+        );
+        assert_eq!(5, inventory.all_items()[2].quantity);
+        assert_eq!(1, inventory.all_items()[1].quantity);
+
+        let stowed: Vec<&str> = inventory
+            .stowed_items()
+            .iter()
+            .map(|item| item.id.as_str())
+            .collect();
+
+        assert_eq!(vec!["rations", "pouch", "ring-of-protection"], stowed);
+    }
+
+    #[test]
+    fn test_inventory_equipped() {
+        let library = Library::load(::std::path::Path::new("../../data/item")).unwrap();
+        let inventory: Inventory = serde_yaml::from_str(
+            "
+items:
+  - {id: longsword, equipped: true}
+  - {id: dagger}
+  - id: backpack
+    contents:
+      - {id: chain-mail, equipped: true}
+      - {id: greatsword, equipped: true}
+",
+        )
+        .unwrap();
+
+        let equipped: Vec<&str> = inventory
+            .equipped(&library)
+            .iter()
+            .map(|(item, _)| item.id.as_str())
+            .collect();
+
+        assert_eq!(vec!["longsword"], equipped);
+    }
+
+    #[test]
+    fn test_inventory_weight() {
+        let library = Library::load(::std::path::Path::new("../../data/item")).unwrap();
+        let inventory: Inventory = serde_yaml::from_str(
+            "
+items:
+  - id: longsword
+  - id: backpack
+    contents:
+      - id: rations
+        quantity: 5
+      - id: not-an-item
+",
+        )
+        .unwrap();
+
+        assert_eq!(3.0 + 5.0 + 2.0 * 5.0, inventory.weight(&library));
+    }
+
+    #[test]
+    fn test_empty_inventory() {
+        assert_eq!(Inventory::default(), serde_yaml::from_str("{}").unwrap());
+    }
+}
@@ -0,0 +1,305 @@
+//! Item
+use super::MetaData;
+use crate::currency::Cost;
+use crate::dice::DiceExpression;
+use crate::rules;
+use crate::units::Distance;
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+
+/// An Item that a Character can carry, such as a weapon, armor or gear
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct Item {
+    /// The Item's metadata
+    pub metadata: MetaData,
+    /// The Item's spec
+    #[serde(alias = "specs")]
+    pub spec: ItemSpec,
+}
+
+/// Item Specification
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct ItemSpec {
+    /// What kind of Item this is
+    #[serde(flatten)]
+    pub kind: ItemKind,
+    /// Weight, in pounds
+    #[serde(default)]
+    pub weight: f32,
+    /// What the Item costs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<Cost>,
+    /// Whether the Item must be attuned to for its magic to work
+    #[serde(default)]
+    pub attunement: bool,
+}
+
+/// What kind of Item something is
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "lowercase", tag = "kind")]
+pub enum ItemKind {
+    /// A Weapon
+    Weapon(WeaponSpec),
+    /// Armor
+    Armor(ArmorSpec),
+    /// A Shield
+    Shield {
+        /// Bonus to Armor Class while wielded
+        ac: i8,
+    },
+    /// Something that holds other Items
+    Container {
+        /// Weight it can hold, in pounds
+        capacity: f32,
+    },
+    /// Anything else
+    Gear,
+}
+
+impl ItemKind {
+    /// Returns the category of the kind, without anything specific to the Item
+    pub fn category(&self) -> ItemCategory {
+        match self {
+            ItemKind::Weapon(_) => ItemCategory::Weapon,
+            ItemKind::Armor(_) => ItemCategory::Armor,
+            ItemKind::Shield { .. } => ItemCategory::Shield,
+            ItemKind::Container { .. } => ItemCategory::Container,
+            ItemKind::Gear => ItemCategory::Gear,
+        }
+    }
+}
+
+/// What kind of Item something is, without anything specific to the Item
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemCategory {
+    /// A Weapon
+    Weapon,
+    /// Armor
+    Armor,
+    /// A Shield
+    Shield,
+    /// Something that holds other Items
+    Container,
+    /// Anything else
+    Gear,
+}
+
+/// Simple or Martial
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum WeaponCategory {
+    /// Simple Weapons
+    Simple,
+    /// Martial Weapons
+    Martial,
+}
+
+/// Weapon Specification
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct WeaponSpec {
+    /// Simple or Martial
+    pub category: WeaponCategory,
+    /// Damage dealt on a hit, before any modifier
+    pub damage: DiceExpression,
+    /// Type of the damage dealt
+    #[serde(rename = "damage-type")]
+    pub damage_type: String,
+    /// Range, for ranged weapons
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<WeaponRange>,
+    /// Properties
+    #[serde(default)]
+    pub properties: Vec<WeaponProperty>,
+}
+
+impl WeaponSpec {
+    /// Returns the Unique ID of the Proficiency needed for every weapon of this category
+    pub fn proficiency(&self) -> &'static str {
+        match self.category {
+            WeaponCategory::Simple => "weapon-simple",
+            WeaponCategory::Martial => "weapon-martial",
+        }
+    }
+
+    /// Returns whether this is a ranged weapon
+    pub fn is_ranged(&self) -> bool {
+        self.range.is_some()
+    }
+
+    /// Returns whether the weapon has `property`
+    pub fn has(&self, property: &WeaponProperty) -> bool {
+        self.properties.contains(property)
+    }
+
+    /// Returns the damage dealt when wielded with two hands, for versatile weapons
+    pub fn versatile(&self) -> Option<&DiceExpression> {
+        self.properties.iter().find_map(|p| match p {
+            WeaponProperty::Versatile(damage) => Some(damage),
+            _ => None,
+        })
+    }
+}
+
+/// Normal and long range of a ranged or thrown weapon
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy, JsonSchema)]
+pub struct WeaponRange {
+    /// Range without disadvantage
+    pub normal: Distance,
+    /// Furthest range, attacking with disadvantage beyond normal range
+    pub long: Distance,
+}
+
+/// A Weapon Property
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum WeaponProperty {
+    /// Uses ammunition
+    Ammunition,
+    /// Can use Strength or Dexterity
+    Finesse,
+    /// Too big for Small creatures to use well
+    Heavy,
+    /// Small enough to fight with two at once
+    Light,
+    /// Can only fire once per action
+    Loading,
+    /// Adds 5 feet to reach
+    Reach,
+    /// Can be thrown
+    Thrown(WeaponRange),
+    /// Needs two hands
+    TwoHanded,
+    /// Can be wielded with two hands for this damage
+    Versatile(DiceExpression),
+}
+
+/// Light, Medium or Heavy
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ArmorCategory {
+    /// Light Armor
+    Light,
+    /// Medium Armor
+    Medium,
+    /// Heavy Armor
+    Heavy,
+}
+
+/// Armor Specification
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct ArmorSpec {
+    /// Light, Medium or Heavy
+    pub category: ArmorCategory,
+    /// Base Armor Class
+    pub ac: i8,
+    /// Strength needed to wear it without being slowed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strength: Option<i8>,
+    /// Whether it gives disadvantage on Stealth checks
+    #[serde(default)]
+    pub stealth: bool,
+}
+
+impl ArmorSpec {
+    /// Returns the Unique ID of the Proficiency needed to wear the armor
+    pub fn proficiency(&self) -> &'static str {
+        match self.category {
+            ArmorCategory::Light => "armor-light",
+            ArmorCategory::Medium => "armor-medium",
+            ArmorCategory::Heavy => "armor-heavy",
+        }
+    }
+
+    /// Returns the Armor Class of a Character wearing it with `dexterity` modifier
+    pub fn armor_class(&self, dexterity: i8) -> i8 {
+        match self.category {
+            ArmorCategory::Light => self.ac.saturating_add(dexterity),
+            ArmorCategory::Medium => self
+                .ac
+                .saturating_add(dexterity.min(rules::MEDIUM_ARMOR_MAX_DEXTERITY)),
+            ArmorCategory::Heavy => self.ac,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weapon_read() {
+        let item: Item = serde_yaml::from_str(
+            "
+metadata:
+  id: longsword
+  name: Longsword
+spec:
+  kind: weapon
+  category: martial
+  damage: 1d8
+  damage-type: slashing
+  properties:
+    - versatile: 1d10
+  weight: 3
+  cost: 15gp
+",
+        )
+        .unwrap();
+
+        let ItemKind::Weapon(weapon) = &item.spec.kind else {
+            panic!("expected a weapon, got {:?}", item.spec.kind);
+        };
+
+        assert_eq!("weapon-martial", weapon.proficiency());
+        assert!(!weapon.is_ranged());
+        assert_eq!(Some(&"1d10".parse().unwrap()), weapon.versatile());
+        assert_eq!(3.0, item.spec.weight);
+        assert_eq!(Some(1_500), item.spec.cost.map(|c| c.copper().unwrap()));
+    }
+
+    #[test]
+    fn test_thrown_weapon_read() {
+        let spec: ItemSpec = serde_yaml::from_str(
+            "
+kind: weapon
+category: simple
+damage: 1d4
+damage-type: piercing
+properties:
+  - finesse
+  - light
+  - thrown:
+      normal: 20ft
+      long: 60ft
+",
+        )
+        .unwrap();
+
+        let ItemKind::Weapon(weapon) = &spec.kind else {
+            panic!("expected a weapon, got {:?}", spec.kind);
+        };
+
+        assert!(weapon.has(&WeaponProperty::Finesse));
+        assert!(weapon.has(&WeaponProperty::Thrown(WeaponRange {
+            normal: Distance(20),
+            long: Distance(60),
+        })));
+        assert_eq!(0.0, spec.weight);
+    }
+
+    #[test]
+    fn test_armor_class() {
+        let armor = |category| ArmorSpec {
+            category,
+            ac: 14,
+            strength: None,
+            stealth: false,
+        };
+
+        assert_eq!(18, armor(ArmorCategory::Light).armor_class(4));
+        assert_eq!(16, armor(ArmorCategory::Medium).armor_class(4));
+        assert_eq!(13, armor(ArmorCategory::Medium).armor_class(-1));
+        assert_eq!(14, armor(ArmorCategory::Heavy).armor_class(4));
+    }
+}
@@ -1,13 +1,24 @@
 //! Assets
+pub mod background;
 pub mod character;
 pub mod class;
+pub mod condition;
+pub mod feat;
+pub mod feature;
+pub mod inventory;
+pub mod item;
+pub mod race;
 
 use std::collections::HashMap;
+use self::character::CharacterAssetGrant;
+use self::condition::{ConditionContext, Conditional};
+use crate::units::Distance;
 use my_macros::SelectEnum;
+use schemars::JsonSchema;
 use serde_derive::{Deserialize, Serialize};
 
 /// MetaData
-#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct MetaData {
     /// Unique Id for this Asset
     pub id: String,
@@ -19,22 +30,178 @@ pub struct MetaData {
     pub description: Option<String>,
     /// Any other fields as needed
     #[serde(flatten, default)]
+    #[schemars(with = "HashMap<String, serde_json::Value>")]
     pub extra: HashMap<String, serde_yaml::Value>,
 }
 
 /// Asset Grants
-#[derive(Debug, PartialEq, Serialize, Deserialize, SelectEnum)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, SelectEnum, Clone, JsonSchema)]
 #[serde(rename_all = "lowercase", tag = "type")]
 pub enum Grant {
     /// Proficiency
     Proficiency {
         /// Unique ID
         id: String
+    },
+    /// Feature
+    Feature {
+        /// Unique ID
+        id: String
+    },
+    /// Language
+    Language {
+        /// Unique ID
+        id: String
+    },
+    /// Trait
+    Trait {
+        /// Unique ID
+        id: String
+    },
+    /// Spell
+    Spell {
+        /// Unique ID
+        id: String
+    },
+    /// Size
+    Size {
+        /// Unique ID
+        id: String
+    },
+    /// Vision, or any other sense
+    Vision {
+        /// Unique ID
+        id: String,
+        /// How far the sense reaches
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        range: Option<Distance>,
+    },
+    /// A speed, or a change to one
+    Speed {
+        /// The kind of movement
+        id: Movement,
+        /// How the speed changes
+        #[serde(flatten)]
+        modifier: SpeedModifier,
+    },
+    /// Advantage on a roll
+    Advantage {
+        /// Unique ID of the roll
+        id: String
+    },
+    /// Disadvantage on a roll
+    Disadvantage {
+        /// Unique ID of the roll
+        id: String
+    },
+}
+
+impl From<&Grant> for CharacterAssetGrant {
+    fn from(grant: &Grant) -> Self {
+        match grant.clone() {
+            Grant::Proficiency { id } => Self::Proficiency { id },
+            Grant::Feature { id } => Self::Feature { id, option: None },
+            Grant::Language { id } => Self::Language { id },
+            Grant::Trait { id } => Self::Trait { id, grants: vec![] },
+            Grant::Spell { id } => Self::Spell { id },
+            Grant::Size { id } => Self::Size { id },
+            Grant::Vision { id, range } => Self::Vision { id, range },
+            Grant::Speed { id, modifier } => Self::Speed { id, modifier },
+            Grant::Advantage { id } => Self::Advantage { id },
+            Grant::Disadvantage { id } => Self::Disadvantage { id },
+        }
+    }
+}
+
+/// Returns the grants whose conditions hold in `ctx`, as [`CharacterAssetGrant`]s
+pub(crate) fn granted<'a>(
+    grants: &'a [Conditional<Grant>],
+    ctx: &'a ConditionContext,
+) -> impl Iterator<Item = CharacterAssetGrant> + 'a {
+    grants
+        .iter()
+        .filter(move |g| g.is_met(ctx))
+        .map(|g| CharacterAssetGrant::from(&g.value))
+}
+
+/// Serde default of quantities, such as of Items, that are one unless written
+pub(crate) fn one() -> u32 {
+    1
+}
+
+/// Returns `id`, such as `sleight-of-hand`, as a title such as `Sleight of Hand`.
+///
+/// Every export names Skills, languages and the like with this, so they read the same.
+pub fn title(id: &str) -> String {
+    id.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .enumerate()
+        .map(|(i, word)| match word {
+            "of" | "the" if i > 0 => String::from(word),
+            _ => {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A value that something adds to a stat on the Character sheet
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy, JsonSchema)]
+#[serde(untagged)]
+pub enum Stat {
+    /// Turns the stat on, such as advantage on a roll
+    Flag(bool),
+    /// Adds to the stat, such as a bonus to a roll
+    Number(i32),
+}
+
+impl Stat {
+    /// Returns the stat with `other` applied, numbers being added together (saturating
+    /// at the bounds of an `i32`) and flags staying set once either is set
+    pub fn combine(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Number(a), Self::Number(b)) => Self::Number(a.saturating_add(b)),
+            (Self::Flag(a), Self::Flag(b)) => Self::Flag(a || b),
+            (_, other) => other,
+        }
     }
 }
 
+/// A way a Character can move, each of which has its own speed
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Movement {
+    /// Walking
+    Walk,
+    /// Flying
+    Fly,
+    /// Climbing
+    Climb,
+    /// Swimming
+    Swim,
+    /// Burrowing
+    Burrow,
+}
+
+/// How a grant changes a speed
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpeedModifier {
+    /// Sets the speed, keeping the highest if it is set more than once
+    Set(Distance),
+    /// Adds to the speed
+    Bonus(Distance),
+    /// Sets the speed to another, such as a climbing speed equal to the walking speed
+    EqualTo(Movement),
+}
+
 /// Spec
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(untagged)]
 pub enum Spec {
     /// Class Spec
@@ -42,16 +209,16 @@ pub enum Spec {
         /// Setters for Class Spec
         set: Setter,
         /// Grants for Class Spec
-        grant: Vec<Grant>,
+        grant: Vec<Conditional<Grant>>,
         /// Selects for Class Spec
         select: Vec<Select>,
     },
 }
 
-/// Die
-#[derive(Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+/// Die, for anything that is a single die rather than a [`crate::dice::DiceExpression`]
+#[derive(Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Clone, Copy, JsonSchema)]
 #[serde(rename_all = "lowercase")]
-pub enum Die { // FIXME: Setup better way to deserialize dice & dice equations
+pub enum Die {
     /// D4
     D4,
     /// D6
@@ -66,8 +233,33 @@ pub enum Die { // FIXME: Setup better way to deserialize dice & dice equations
     D20,
 }
 
+impl Die {
+    /// Returns the number of sides on the Die
+    pub fn sides(&self) -> u32 {
+        match self {
+            Die::D4 => 4,
+            Die::D6 => 6,
+            Die::D8 => 8,
+            Die::D10 => 10,
+            Die::D12 => 12,
+            Die::D20 => 20,
+        }
+    }
+}
+
+impl From<Die> for crate::dice::DiceExpression {
+    fn from(die: Die) -> Self {
+        crate::dice::Term::Dice {
+            count: 1,
+            sides: die.sides(),
+            keep: None,
+        }
+        .into()
+    }
+}
+
 /// Setter
-#[derive(Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(untagged)]
 pub enum Setter { // TODO: have set field in Spec be deseriealized as a HS of enums
     /// Class Setter
@@ -75,11 +267,14 @@ pub enum Setter { // TODO: have set field in Spec be deseriealized as a HS of en
         /// Class Setter Hit Dice
         #[serde(rename = "hit-dice")]
         hit_dice: Die,
+        /// Class Setter level at which a SubClass is chosen
+        #[serde(rename = "subclass-level", default, skip_serializing_if = "Option::is_none")]
+        subclass_level: Option<i8>,
     },
 }
 
 /// Asset
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(rename_all = "lowercase", tag = "type")]
 pub enum Asset {
     /// Character Asset
@@ -101,4 +296,88 @@ pub enum Asset {
         /// MetaData
         metadata: MetaData,
     },
+    /// Size Asset
+    Size {
+        /// MetaData
+        metadata: MetaData,
+    },
+    /// Vision Asset
+    Vision {
+        /// MetaData
+        metadata: MetaData,
+    },
+    /// Feat Asset
+    Feat(self::feat::Feat),
+    /// Race Asset
+    Race(self::race::Race),
+    /// SubRace Asset
+    #[serde(rename = "sub-race")]
+    SubRace(self::race::SubRace),
+    /// Trait Asset
+    Trait(self::race::Trait),
+    /// Feature Asset
+    Feature(self::feature::Feature),
+    /// Background Asset
+    Background(self::background::Background),
+    /// SubClass Asset
+    #[serde(rename = "sub-class")]
+    SubClass(self::class::SubClass),
+    /// Item Asset
+    Item(self::item::Item),
+}
+
+impl Asset {
+    /// Returns the name of this Asset's type, as written in its `type` field
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Character(_) => "character",
+            Self::Class { .. } => "class",
+            Self::Proficiency { .. } => "proficiency",
+            Self::Language { .. } => "language",
+            Self::Size { .. } => "size",
+            Self::Vision { .. } => "vision",
+            Self::Feat(_) => "feat",
+            Self::Race(_) => "race",
+            Self::SubRace(_) => "sub-race",
+            Self::Trait(_) => "trait",
+            Self::Feature(_) => "feature",
+            Self::Background(_) => "background",
+            Self::SubClass(_) => "sub-class",
+            Self::Item(_) => "item",
+        }
+    }
+
+    /// Returns this Asset's MetaData
+    pub fn metadata(&self) -> &MetaData {
+        match self {
+            Self::Character(character) => &character.metadata,
+            Self::Class { metadata, .. } => metadata,
+            Self::Proficiency { metadata } => metadata,
+            Self::Language { metadata } => metadata,
+            Self::Size { metadata } => metadata,
+            Self::Vision { metadata } => metadata,
+            Self::Feat(feat) => &feat.metadata,
+            Self::Race(race) => &race.metadata,
+            Self::SubRace(sub_race) => &sub_race.metadata,
+            Self::Trait(t) => &t.metadata,
+            Self::Feature(feature) => &feature.metadata,
+            Self::Background(background) => &background.metadata,
+            Self::SubClass(sub_class) => &sub_class.metadata,
+            Self::Item(item) => &item.metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title() {
+        assert_eq!("Sleight of Hand", title("sleight-of-hand"));
+        assert_eq!("Misty Step", title("misty-step"));
+        assert_eq!("Armored Ac", title("armored.ac"));
+        assert_eq!("The Elvish", title("the-elvish"));
+        assert_eq!("", title(""));
+    }
 }
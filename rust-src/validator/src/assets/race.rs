@@ -0,0 +1,167 @@
+//! Race, SubRace and Trait
+use std::collections::BTreeMap;
+
+use super::character::{Ability, CharacterAssetGrant};
+use super::condition::{ConditionContext, Conditional};
+use super::{granted, Grant, MetaData, Movement, Select, SpeedModifier};
+use crate::units::Distance;
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+
+/// A Race that a Character can be
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct Race {
+    /// The Race's metadata
+    pub metadata: MetaData,
+    /// The Race's spec
+    #[serde(default, alias = "specs")]
+    pub spec: RaceSpec,
+}
+
+/// A SubRace of a [`Race`]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct SubRace {
+    /// The SubRace's metadata
+    pub metadata: MetaData,
+    /// The SubRace's spec
+    #[serde(alias = "specs")]
+    pub spec: SubRaceSpec,
+}
+
+/// A Trait, granted by a Race or SubRace
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct Trait {
+    /// The Trait's metadata
+    pub metadata: MetaData,
+    /// The Trait's spec
+    #[serde(default, alias = "specs")]
+    pub spec: TraitSpec,
+}
+
+/// Race Specification
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone, JsonSchema)]
+pub struct RaceSpec {
+    /// Unique ID of the Size of the Race
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+    /// Speeds of the Race
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub speed: BTreeMap<Movement, Distance>,
+    /// Bonuses to Ability Scores
+    #[serde(default)]
+    pub ability: BTreeMap<Ability, i8>,
+    /// Grants for Race Spec
+    #[serde(default)]
+    pub grant: Vec<Conditional<Grant>>,
+    /// Selects for Race Spec
+    #[serde(default)]
+    pub select: Vec<Select>,
+}
+
+impl RaceSpec {
+    /// Returns the [`CharacterAssetGrant`]s this spec gives a Character in `ctx`
+    pub fn grants(&self, ctx: &ConditionContext) -> Vec<CharacterAssetGrant> {
+        self.size
+            .iter()
+            .map(|id| CharacterAssetGrant::Size { id: id.clone() })
+            .chain(
+                self.speed
+                    .iter()
+                    .map(|(&id, &speed)| CharacterAssetGrant::Speed {
+                        id,
+                        modifier: SpeedModifier::Set(speed),
+                    }),
+            )
+            .chain(
+                self.ability
+                    .iter()
+                    .map(|(&id, &add)| CharacterAssetGrant::AbilityScore { id, add }),
+            )
+            .chain(granted(&self.grant, ctx))
+            .collect()
+    }
+}
+
+/// SubRace Specification
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct SubRaceSpec {
+    /// Unique ID of the Race this is a SubRace of
+    pub race: String,
+    /// Everything else a SubRace has in common with a Race
+    #[serde(flatten)]
+    pub spec: RaceSpec,
+}
+
+/// Trait Specification
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Clone, JsonSchema)]
+pub struct TraitSpec {
+    /// Grants for Trait Spec
+    #[serde(default)]
+    pub grant: Vec<Conditional<Grant>>,
+    /// Selects for Trait Spec
+    #[serde(default)]
+    pub select: Vec<Select>,
+}
+
+impl TraitSpec {
+    /// Returns the [`CharacterAssetGrant`]s this spec gives a Character in `ctx`
+    pub fn grants(&self, ctx: &ConditionContext) -> Vec<CharacterAssetGrant> {
+        granted(&self.grant, ctx).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::Asset;
+
+    #[test]
+    fn test_elf_traits_read() {
+        let f = ::std::fs::read_to_string("../../data/trait/elf.yml").unwrap();
+        let traits: Vec<Asset> = serde_yaml::Deserializer::from_str(&f)
+            .map(|doc| serde::Deserialize::deserialize(doc).unwrap())
+            .collect();
+
+        let Asset::Trait(fey_ancestry) = &traits[1] else {
+            panic!("expected a trait, got {:?}", traits[1]);
+        };
+
+        assert_eq!(3, traits.len());
+        assert_eq!("fey-ancestry", fey_ancestry.metadata.id);
+        assert_eq!(
+            vec![Conditional::from(Grant::Advantage {
+                id: String::from("saving-charmed")
+            })],
+            fey_ancestry.spec.grant
+        );
+    }
+
+    #[test]
+    fn test_sub_race_read() {
+        let sub_race: SubRace = serde_yaml::from_str(
+            "
+metadata:
+  id: high-elf
+  name: High Elf
+spec:
+  race: elf
+  ability:
+    intelligence: 1
+  grant:
+    - type: trait
+      id: cantrip
+",
+        )
+        .unwrap();
+
+        assert_eq!("elf", sub_race.spec.race);
+        assert_eq!(None, sub_race.spec.spec.size);
+        assert_eq!(Some(&1), sub_race.spec.spec.ability.get(&Ability::Intelligence));
+        assert_eq!(
+            vec![Conditional::from(Grant::Trait {
+                id: String::from("cantrip")
+            })],
+            sub_race.spec.spec.grant
+        );
+    }
+}
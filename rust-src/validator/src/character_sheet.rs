@@ -1,38 +1,407 @@
 //! Module to deal with final Character Data, to be directly used where it is needed.
+use std::collections::BTreeMap;
+use std::ops::{Index, IndexMut};
+
+use schemars::JsonSchema;
+use serde::{Serialize, Serializer};
+use serde_derive::Deserialize;
+
 use crate::assets::{
-    character::{Abilities, Character, CharacterAssetGrant, FetchDefFromAPI, GetAllGrants, ASI},
-    Asset, MetaData,
+    character::{
+        Abilities, Ability, Character, CharacterAssetGrant, Encumbrance, FetchDefFromAPI, Skill,
+        ASI,
+    },
+    item::{Item, ItemCategory, ItemKind, WeaponProperty, WeaponRange, WeaponSpec},
+    Asset, Die, MetaData, Movement, SpeedModifier, Stat,
 };
+use crate::dice::{DiceContext, DiceExpression};
+use crate::library::Library;
+use crate::rules;
+use crate::units::Distance;
 
-/// This Struct is directly used when filling out fields in the ui character sheet
-#[derive(Debug, PartialEq, Clone)]
+/// This Struct is directly used when filling out fields in the ui character sheet.
+///
+/// It serializes with everything computed from the Character, such as the
+/// total and modifier of each Ability Score, so the ui doesn't need the rules.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CharacterSheet {
+    /// Name of the Character
+    name: String,
+    /// Total level, of every class
     level: i8,
+    /// Experience points
+    experience: u32,
+    /// Experience points needed for the next level, if there is one
+    experience_to_next_level: Option<u32>,
+    /// Proficiency bonus
+    proficiency_bonus: i8,
+    /// Armor Class
     armor_class: i8,
+    /// Unique ID of the size
     size: String,
+    /// Unique IDs of the languages known
     languages: Vec<String>,
+    /// Ability Scores
     ability_scores: AbilityScores,
+    /// Every Skill
+    skills: BTreeMap<Skill, SkillCheck>,
+    /// Proficiencies
     proficiencies: Vec<MetaData>,
+    /// Stats added to by grants, by their Unique ID
+    stats: BTreeMap<String, Stat>,
+    /// Senses
+    senses: Senses,
+    /// Speeds
+    speeds: BTreeMap<Movement, Distance>,
+    /// Attacks with equipped weapons
+    attacks: Vec<Attack>,
+    /// What is carried, and what can be
+    carrying: Carrying,
+    /// Maximum hit points
+    hit_points: i32,
+    /// Hit Dice of each class
+    hit_dice: Vec<HitDice>,
+    /// Features and Traits, along with the options chosen for them
+    features: Vec<MetaData>,
+    /// Unique IDs of the Spells known
+    spells: Vec<String>,
+    /// Items carried
+    items: Vec<CarriedItem>,
+}
+
+/// Checks with a Skill
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub struct SkillCheck {
+    /// Modifier of the check
+    pub modifier: i8,
+    /// Whether the Character is proficient in the Skill
+    pub proficient: bool,
+}
+
+/// The Hit Dice a Character has from one of their classes
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HitDice {
+    /// Unique ID of the class
+    pub class: String,
+    /// Name of the class
+    pub name: String,
+    /// The Hit Die of the class
+    pub die: Die,
+    /// How many of the Hit Die, one for every level in the class
+    pub count: i8,
+}
+
+/// An Item the Character is carrying
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CarriedItem {
+    /// Unique ID of the Item
+    pub id: String,
+    /// Name of the Item
+    pub name: String,
+    /// What kind of Item it is
+    pub category: ItemCategory,
+    /// How many of the Item
+    pub quantity: u32,
+    /// Weight of one of the Item, in pounds
+    pub weight: f32,
+    /// Whether the Item is worn or wielded
+    pub equipped: bool,
+    /// Whether the Character is attuned to the Item
+    pub attuned: bool,
+}
+
+/// An attack with an equipped weapon
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Attack {
+    /// Unique ID of the weapon
+    pub id: String,
+    /// Name of the weapon
+    pub name: String,
+    /// Bonus to the attack roll
+    pub bonus: i8,
+    /// Damage dealt on a hit
+    pub damage: DiceExpression,
+    /// Damage dealt when wielded with two hands, for versatile weapons
+    pub two_handed_damage: Option<DiceExpression>,
+    /// Type of the damage dealt
+    pub damage_type: String,
+    /// Whether the weapon needs two hands
+    pub two_handed: bool,
+    /// Normal and long range, for ranged and thrown weapons
+    pub range: Option<WeaponRange>,
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
-struct AbilityRoll {
+/// Stat path of a bonus to Armor Class
+const AC_STAT: &str = "ac";
+
+/// Stat path of a bonus to maximum hit points
+const HP_STAT: &str = "hp";
+
+/// Stat path of a bonus to maximum hit points for every level
+const HP_LEVEL_STAT: &str = "hp.level";
+
+/// Stat paths of bonuses to every attack roll and damage roll
+const ATTACK_STATS: [&str; 2] = ["attack", "damage"];
+
+/// Stat paths of bonuses to melee weapon attack rolls and damage rolls
+const MELEE_ATTACK_STATS: [&str; 2] = ["melee.attack", "melee.damage"];
+
+/// Stat paths of bonuses to ranged weapon attack rolls and damage rolls
+const RANGED_ATTACK_STATS: [&str; 2] = ["ranged.attack", "ranged.damage"];
+
+/// Stat path of a bonus to Armor Class while wearing armor
+const ARMORED_AC_STAT: &str = "armored.ac";
+
+/// Returns the number `stats` has at `path`, or 0 if it has none.
+///
+/// Numbers too large for an `i8` are saturated rather than wrapped.
+fn stat_bonus(stats: &BTreeMap<String, Stat>, path: &str) -> i8 {
+    match stats.get(path) {
+        Some(Stat::Number(bonus)) => {
+            i8::try_from(*bonus).unwrap_or(if *bonus < 0 { i8::MIN } else { i8::MAX })
+        }
+        _ => 0,
+    }
+}
+
+/// Returns the speeds `grants` give, each `penalty` feet slower
+fn speeds(grants: &[CharacterAssetGrant], penalty: u16) -> BTreeMap<Movement, Distance> {
+    let modifiers = || {
+        grants.iter().filter_map(|grant| match grant {
+            CharacterAssetGrant::Speed { id, modifier } => Some((*id, *modifier)),
+            _ => None,
+        })
+    };
+
+    let mut speeds: BTreeMap<Movement, Distance> = BTreeMap::new();
+
+    // Speeds are set before anything is added to them
+    for (id, modifier) in modifiers() {
+        if let SpeedModifier::Set(speed) = modifier {
+            let best = speeds.get(&id).map_or(speed, |&s| s.max(speed));
+            speeds.insert(id, best);
+        }
+    }
+
+    // Bonuses only apply to speeds the Character has
+    for (id, modifier) in modifiers() {
+        if let (SpeedModifier::Bonus(bonus), Some(speed)) = (modifier, speeds.get_mut(&id)) {
+            speed.0 = speed.0.saturating_add(bonus.0);
+        }
+    }
+
+    // Then speeds equal to another are copied over, once the other is final
+    let equal: Vec<(Movement, Distance)> = modifiers()
+        .filter_map(|(id, modifier)| match modifier {
+            SpeedModifier::EqualTo(other) => speeds.get(&other).map(|&speed| (id, speed)),
+            _ => None,
+        })
+        .collect();
+    for (id, speed) in equal {
+        let best = speeds.get(&id).map_or(speed, |&s| s.max(speed));
+        speeds.insert(id, best);
+    }
+
+    speeds
+        .into_iter()
+        .map(|(id, speed)| (id, Distance(speed.0.saturating_sub(penalty))))
+        .collect()
+}
+
+/// The senses of a Character
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct Senses {
+    /// The best range of each sense, by its Unique ID
+    ranges: BTreeMap<String, Option<Distance>>,
+    passive_perception: i8,
+}
+
+impl Senses {
+    /// Adds the sense `id`, keeping the best range if it is already had
+    fn add(&mut self, id: &str, range: Option<Distance>) {
+        let best = self.ranges.get(id).copied().flatten().max(range);
+        self.ranges.insert(String::from(id), best);
+    }
+
+    /// Returns the range of the sense `id`, if it is had.
+    ///
+    /// The range is [`None`] for senses that were granted without one.
+    pub fn range(&self, id: &str) -> Option<Option<Distance>> {
+        self.ranges.get(id).copied()
+    }
+
+    /// Returns every sense, by its Unique ID
+    pub fn ranges(&self) -> &BTreeMap<String, Option<Distance>> {
+        &self.ranges
+    }
+
+    /// Returns the Character's passive Perception
+    pub fn passive_perception(&self) -> i8 {
+        self.passive_perception
+    }
+}
+
+/// How much a Character carries, and how much they can
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub struct Carrying {
+    weight: f32,
+    capacity: f32,
+    push_drag_lift: f32,
+    encumbrance: EncumbranceLevel,
+}
+
+impl Carrying {
+    /// Returns what a Character with `strength` and of the size `size`
+    /// carrying `weight` pounds can carry, using the `rules` for encumbrance
+    fn new(weight: f32, strength: i8, size: &str, rules: Encumbrance) -> Self {
+        let strength = f32::from(strength.max(0)) * rules::size_carrying_multiplier(size);
+
+        let encumbrance = match rules {
+            Encumbrance::Standard => EncumbranceLevel::Unencumbered,
+            Encumbrance::Variant if weight > strength * rules::HEAVILY_ENCUMBERED_PER_STRENGTH => {
+                EncumbranceLevel::HeavilyEncumbered
+            }
+            Encumbrance::Variant if weight > strength * rules::ENCUMBERED_PER_STRENGTH => {
+                EncumbranceLevel::Encumbered
+            }
+            Encumbrance::Variant => EncumbranceLevel::Unencumbered,
+        };
+
+        Self {
+            weight,
+            capacity: strength * rules::CARRYING_CAPACITY_PER_STRENGTH,
+            push_drag_lift: strength * rules::PUSH_DRAG_LIFT_PER_STRENGTH,
+            encumbrance,
+        }
+    }
+
+    /// Returns the weight of everything carried, in pounds
+    pub fn weight(&self) -> f32 {
+        self.weight
+    }
+
+    /// Returns the most weight that can be carried, in pounds
+    pub fn capacity(&self) -> f32 {
+        self.capacity
+    }
+
+    /// Returns the most weight that can be pushed, dragged or lifted, in pounds
+    pub fn push_drag_lift(&self) -> f32 {
+        self.push_drag_lift
+    }
+
+    /// Returns whether more is carried than the carrying capacity
+    pub fn is_over_capacity(&self) -> bool {
+        self.weight > self.capacity
+    }
+
+    /// Returns how encumbered the Character is
+    pub fn encumbrance(&self) -> EncumbranceLevel {
+        self.encumbrance
+    }
+}
+
+/// How encumbered a Character is, with the variant encumbrance rules
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum EncumbranceLevel {
+    /// Not slowed down
+    #[default]
+    Unencumbered,
+    /// 10 feet slower
+    Encumbered,
+    /// 20 feet slower, with disadvantage on ability checks, attack rolls and
+    /// saving throws using Strength, Dexterity or Constitution
+    HeavilyEncumbered,
+}
+
+impl EncumbranceLevel {
+    /// Returns how many feet slower the Character is
+    pub fn speed_penalty(&self) -> u16 {
+        match self {
+            Self::Unencumbered => 0,
+            Self::Encumbered => rules::ENCUMBERED_SPEED_PENALTY,
+            Self::HeavilyEncumbered => rules::HEAVILY_ENCUMBERED_SPEED_PENALTY,
+        }
+    }
+}
+
+/// An Ability Score, from the base score and what is added to it.
+///
+/// It serializes with its total and modifier, which are ignored when it is
+/// deserialized.
+#[derive(Debug, PartialEq, Clone, Copy, Deserialize)]
+pub struct AbilityRoll {
     base: i8,
     mods: i8,
 }
 
 impl AbilityRoll {
+    /// Returns the base score
+    pub fn base(&self) -> i8 {
+        self.base
+    }
+
+    /// Returns what is added to the base score
+    pub fn mods(&self) -> i8 {
+        self.mods
+    }
+
+    /// Returns the score
     pub fn total(&self) -> i8 {
-        self.base + self.mods
+        self.base.saturating_add(self.mods)
     }
 
+    /// Returns the modifier of the score
     pub fn modifier(&self) -> i8 {
-        (self.total() - 10) / 2
+        rules::ability_modifier(self.total())
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
-struct AbilityScores {
+/// What an [`AbilityRoll`] serializes as
+#[derive(Serialize, JsonSchema)]
+#[schemars(rename = "AbilityRoll")]
+struct AbilityRollFields {
+    /// The base score
+    base: i8,
+    /// What is added to the base score
+    mods: i8,
+    /// The score
+    total: i8,
+    /// Modifier of the score
+    modifier: i8,
+}
+
+impl From<AbilityRoll> for AbilityRollFields {
+    fn from(roll: AbilityRoll) -> Self {
+        Self {
+            base: roll.base,
+            mods: roll.mods,
+            total: roll.total(),
+            modifier: roll.modifier(),
+        }
+    }
+}
+
+impl Serialize for AbilityRoll {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        AbilityRollFields::from(*self).serialize(serializer)
+    }
+}
+
+impl JsonSchema for AbilityRoll {
+    fn schema_name() -> String {
+        AbilityRollFields::schema_name()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        AbilityRollFields::json_schema(gen)
+    }
+}
+
+/// The six Ability Scores of a Character
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub struct AbilityScores {
     strength: AbilityRoll,
     dexterity: AbilityRoll,
     constitution: AbilityRoll,
@@ -73,22 +442,268 @@ impl From<Abilities> for AbilityScores {
 }
 
 impl AbilityScores {
-    pub fn add_to_mod(&mut self, ability: &str, v: i8) {
-        match ability {
-            "strength" => self.strength.mods += v,
-            "dexterity" => self.dexterity.mods += v,
-            "constitution" => self.constitution.mods += v,
-            "intelligence" => self.intelligence.mods += v,
-            "wisdom" => self.wisdom.mods += v,
-            "charisma" => self.charisma.mods += v,
+    /// Returns `base` with every ASI and AbilityScore grant in `grants` applied
+    pub fn new(base: Abilities, grants: &[CharacterAssetGrant]) -> Self {
+        let mut ability_scores: AbilityScores = base.into();
+
+        grants.iter().for_each(|grant| match grant {
+            CharacterAssetGrant::ASI(ASI::Ability(a, b)) => {
+                if let Some(bval) = b {
+                    ability_scores.add_to_mod(*a, 1);
+                    ability_scores.add_to_mod(*bval, 1);
+                } else {
+                    ability_scores.add_to_mod(*a, 2);
+                }
+            }
+            CharacterAssetGrant::AbilityScore { id, add: modifier } => {
+                ability_scores.add_to_mod(*id, *modifier);
+            }
             _ => {}
+        });
+
+        ability_scores
+    }
+
+    /// Returns the total of every ability
+    pub fn totals(&self) -> Abilities {
+        Abilities {
+            strength: self.strength.total(),
+            dexterity: self.dexterity.total(),
+            constitution: self.constitution.total(),
+            intelligence: self.intelligence.total(),
+            wisdom: self.wisdom.total(),
+            charisma: self.charisma.total(),
+        }
+    }
+
+    pub(crate) fn add_to_mod(&mut self, ability: Ability, v: i8) {
+        self[ability].mods = self[ability].mods.saturating_add(v);
+    }
+}
+
+impl Index<Ability> for AbilityScores {
+    type Output = AbilityRoll;
+
+    fn index(&self, ability: Ability) -> &Self::Output {
+        match ability {
+            Ability::Strength => &self.strength,
+            Ability::Dexterity => &self.dexterity,
+            Ability::Constitution => &self.constitution,
+            Ability::Intelligence => &self.intelligence,
+            Ability::Wisdom => &self.wisdom,
+            Ability::Charisma => &self.charisma,
+        }
+    }
+}
+
+impl IndexMut<Ability> for AbilityScores {
+    fn index_mut(&mut self, ability: Ability) -> &mut Self::Output {
+        match ability {
+            Ability::Strength => &mut self.strength,
+            Ability::Dexterity => &mut self.dexterity,
+            Ability::Constitution => &mut self.constitution,
+            Ability::Intelligence => &mut self.intelligence,
+            Ability::Wisdom => &mut self.wisdom,
+            Ability::Charisma => &mut self.charisma,
+        }
+    }
+}
+
+impl CharacterSheet {
+    /// Returns the Character's name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the Character's level
+    pub fn level(&self) -> i8 {
+        self.level
+    }
+
+    /// Returns the Character's experience points
+    pub fn experience(&self) -> u32 {
+        self.experience
+    }
+
+    /// Returns the Character's proficiency bonus
+    pub fn proficiency_bonus(&self) -> i8 {
+        self.proficiency_bonus
+    }
+
+    /// Returns the Character's Armor Class
+    pub fn armor_class(&self) -> i8 {
+        self.armor_class
+    }
+
+    /// Returns the Unique ID of the Character's size
+    pub fn size(&self) -> &str {
+        &self.size
+    }
+
+    /// Returns the Unique IDs of the languages the Character knows
+    pub fn languages(&self) -> &[String] {
+        &self.languages
+    }
+
+    /// Returns the Character's Ability Scores
+    pub fn ability_scores(&self) -> &AbilityScores {
+        &self.ability_scores
+    }
+
+    /// Returns the score of `ability`
+    pub fn ability_score(&self, ability: Ability) -> i8 {
+        self.ability_scores[ability].total()
+    }
+
+    /// Returns the modifier of `ability`
+    pub fn ability_modifier(&self, ability: Ability) -> i8 {
+        self.ability_scores[ability].modifier()
+    }
+
+    /// Returns whether the Character has the Proficiency `id`
+    pub fn is_proficient(&self, id: &str) -> bool {
+        self.proficiencies.iter().any(|p| p.id == id)
+    }
+
+    /// Returns the modifier of checks with `skill`
+    pub fn skill_modifier(&self, skill: Skill) -> i8 {
+        self.skills.get(&skill).map_or(0, |check| check.modifier)
+    }
+
+    /// Returns checks with every Skill
+    pub fn skills(&self) -> &BTreeMap<Skill, SkillCheck> {
+        &self.skills
+    }
+
+    /// Returns checks with `skill`, from the Ability Scores and proficiencies
+    fn skill_check(&self, skill: Skill) -> SkillCheck {
+        let proficient = self.is_proficient(&skill.proficiency());
+        let proficiency = if proficient {
+            self.proficiency_bonus()
+        } else {
+            0
         };
+
+        SkillCheck {
+            modifier: self.ability_modifier(skill.ability()).saturating_add(proficiency),
+            proficient,
+        }
+    }
+
+    /// Returns the Character's senses
+    pub fn senses(&self) -> &Senses {
+        &self.senses
+    }
+
+    /// Returns an attack with every equipped weapon
+    pub fn attacks(&self) -> &[Attack] {
+        &self.attacks
+    }
+
+    /// Returns the attack made with `item`
+    fn attack(&self, item: &Item, weapon: &WeaponSpec) -> Attack {
+        let ability = if weapon.has(&WeaponProperty::Finesse) {
+            self.ability_modifier(Ability::Strength)
+                .max(self.ability_modifier(Ability::Dexterity))
+        } else if weapon.is_ranged() {
+            self.ability_modifier(Ability::Dexterity)
+        } else {
+            self.ability_modifier(Ability::Strength)
+        };
+
+        let proficient =
+            self.is_proficient(weapon.proficiency()) || self.is_proficient(&item.metadata.id);
+        let proficiency = if proficient {
+            self.proficiency_bonus()
+        } else {
+            0
+        };
+
+        let [attack_stat, damage_stat] = if weapon.is_ranged() {
+            RANGED_ATTACK_STATS
+        } else {
+            MELEE_ATTACK_STATS
+        };
+        let attack_bonus = stat_bonus(&self.stats, attack_stat)
+            .saturating_add(stat_bonus(&self.stats, ATTACK_STATS[0]));
+        let damage_bonus = stat_bonus(&self.stats, damage_stat)
+            .saturating_add(stat_bonus(&self.stats, ATTACK_STATS[1]));
+
+        let damage = |dice: &DiceExpression| {
+            dice.clone()
+                .plus(i32::from(ability) + i32::from(damage_bonus))
+        };
+
+        Attack {
+            id: item.metadata.id.clone(),
+            name: item.metadata.name.clone(),
+            bonus: ability
+                .saturating_add(proficiency)
+                .saturating_add(attack_bonus),
+            damage: damage(&weapon.damage),
+            two_handed_damage: weapon.versatile().map(damage),
+            damage_type: weapon.damage_type.clone(),
+            two_handed: weapon.has(&WeaponProperty::TwoHanded),
+            range: weapon.range.or_else(|| {
+                weapon.properties.iter().find_map(|p| match p {
+                    WeaponProperty::Thrown(range) => Some(*range),
+                    _ => None,
+                })
+            }),
+        }
+    }
+
+    /// Returns the Character's maximum hit points
+    pub fn hit_points(&self) -> i32 {
+        self.hit_points
+    }
+
+    /// Returns the Character's Hit Dice, for each class
+    pub fn hit_dice(&self) -> &[HitDice] {
+        &self.hit_dice
+    }
+
+    /// Returns every Feature and Trait the Character has, along with the
+    /// options chosen for them
+    pub fn features(&self) -> &[MetaData] {
+        &self.features
+    }
+
+    /// Returns the Unique IDs of the Spells the Character knows
+    pub fn spells(&self) -> &[String] {
+        &self.spells
+    }
+
+    /// Returns every Item the Character is carrying
+    pub fn items(&self) -> &[CarriedItem] {
+        &self.items
+    }
+
+    /// Returns what the Character carries and can carry
+    pub fn carrying(&self) -> &Carrying {
+        &self.carrying
+    }
+
+    /// Returns the Character's speeds
+    pub fn speeds(&self) -> &BTreeMap<Movement, Distance> {
+        &self.speeds
     }
 }
 
-// TODO: Character Sheet Trait to write to rptok file, technically it's just a serde thing,
-// might need the trait just to define the methods to get the macro text n shit, but ye.
-// The rptok writer should probably be done in a separate lib.
+/// Lets Dice Expressions like `1d20+str+prof` be resolved against the sheet
+impl DiceContext for CharacterSheet {
+    fn ability_modifier(&self, ability: Ability) -> i32 {
+        CharacterSheet::ability_modifier(self, ability).into()
+    }
+
+    fn proficiency_bonus(&self) -> i32 {
+        CharacterSheet::proficiency_bonus(self).into()
+    }
+
+    fn level(&self) -> i32 {
+        CharacterSheet::level(self).into()
+    }
+}
 
 /// This will not validate the Character struct, it will just convert it to a format
 /// that the ui will be able to use.
@@ -96,10 +711,21 @@ impl TryFrom<Character> for CharacterSheet {
     type Error = ();
 
     fn try_from(value: Character) -> Result<Self, Self::Error> {
+        Ok(Self::build(&value, &Library::new()))
+    }
+}
+
+impl CharacterSheet {
+    /// Builds the sheet of `value`, using the Assets in `library` for
+    /// everything the Character references.
+    ///
+    /// This will not validate the Character, see [`crate::validation::Validate`].
+    pub fn build(value: &Character, library: &Library) -> Self {
         // First, we need to get all the base values not given by grant hell
+        let grants = value.grants(library);
 
         // Get Ability Scores
-        let mut ability_scores: AbilityScores = value.spec.abilities.into();
+        let ability_scores = AbilityScores::new(value.spec.abilities, &grants);
 
         let mut size: String = String::default();
 
@@ -111,49 +737,214 @@ impl TryFrom<Character> for CharacterSheet {
 
         let mut proficiencies: Vec<MetaData> = vec![];
 
+        let mut stats: BTreeMap<String, Stat> = BTreeMap::new();
+
+        let mut senses = Senses::default();
+
+        let mut features: Vec<MetaData> = vec![];
+
+        let mut spells: Vec<String> = vec![];
+
         // Get Character Level
         let level = value.level();
 
+        let experience = value.spec.experience;
+        let experience_to_next_level = value.experience_to_next_level();
+
         // Now have grant hell work its magic!
-        value.all_grants().iter().for_each(|grant| {
+        grants.iter().for_each(|grant| {
             match grant {
-                CharacterAssetGrant::ASI(ASI::Ability(a, b)) => {
-                    if let Some(bval) = b {
-                        ability_scores.add_to_mod(a, 1);
-                        ability_scores.add_to_mod(bval, 1);
-                    } else {
-                        ability_scores.add_to_mod(a, 2);
-                    }
-                }
                 CharacterAssetGrant::Size { id } => {
                     size = String::from(id);
                 }
-                CharacterAssetGrant::AbilityScore { id, add: modifier } => {
-                    ability_scores.add_to_mod(id, *modifier);
-                }
-                CharacterAssetGrant::Language { id } => {
+                CharacterAssetGrant::Language { id } if !languages.contains(id) => {
                     languages.push(String::from(id));
                 }
-                CharacterAssetGrant::Proficiency { .. } => {
-                    if let Asset::Proficiency { metadata } = grant.fetch_def().unwrap() {
-                        proficiencies.push(metadata);
+                CharacterAssetGrant::Proficiency { id } => {
+                    let metadata = match library.get("proficiency", id) {
+                        Some(asset) => Some(asset.metadata().clone()),
+                        None => match grant.fetch_def() {
+                            Ok(Asset::Proficiency { metadata }) => Some(metadata),
+                            _ => None,
+                        },
                     };
+
+                    if let Some(metadata) = metadata {
+                        if !proficiencies.iter().any(|p| p.id == metadata.id) {
+                            proficiencies.push(metadata);
+                        }
+                    }
+                }
+                CharacterAssetGrant::Vision { id, range } => {
+                    senses.add(id, *range);
+                }
+                CharacterAssetGrant::Stat { id, value } => {
+                    let stat = stats.get(id).map_or(*value, |stat| stat.combine(*value));
+                    stats.insert(id.clone(), stat);
+                }
+                CharacterAssetGrant::Feature { id, option } => {
+                    if let Some(feature) = library.feature(id) {
+                        let option = option.as_deref().and_then(|o| feature.option(o));
+                        for metadata in [Some(&feature.metadata), option.map(|o| &o.metadata)]
+                            .into_iter()
+                            .flatten()
+                        {
+                            if !features.iter().any(|f| f.id == metadata.id) {
+                                features.push(metadata.clone());
+                            }
+                        }
+                    }
+                }
+                CharacterAssetGrant::Trait { id, .. } => {
+                    if let Some(race_trait) = library.race_trait(id) {
+                        if !features.iter().any(|f| f.id == race_trait.metadata.id) {
+                            features.push(race_trait.metadata.clone());
+                        }
+                    }
+                }
+                CharacterAssetGrant::Spell { id } if !spells.contains(id) => {
+                    spells.push(String::from(id));
                 }
                 _ => {}
             };
         });
 
+        let equipped = value.spec.inventory.equipped(library);
+        let armor = equipped.iter().find_map(|(_, item)| match &item.spec.kind {
+            ItemKind::Armor(armor) => Some(armor),
+            _ => None,
+        });
+        let shield = equipped
+            .iter()
+            .filter_map(|(_, item)| match item.spec.kind {
+                ItemKind::Shield { ac } => Some(ac),
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default();
+
+        let carrying = Carrying::new(
+            value.spec.inventory.weight(library),
+            ability_scores.strength.total(),
+            &size,
+            value.spec.options.encumbrance,
+        );
+
+        let speeds = speeds(
+            &grants,
+            rules::armor_speed_penalty(
+                ability_scores.strength.total(),
+                armor.and_then(|armor| armor.strength),
+            ) + carrying.encumbrance.speed_penalty(),
+        );
+
         // Calculate the base ac value and add it to w/ever we already have from grants
-        armor_class += 10 + ability_scores.dexterity.modifier();
+        let dexterity = ability_scores.dexterity.modifier();
+        let base_armor_class = match armor {
+            Some(armor) => armor
+                .armor_class(dexterity)
+                .saturating_add(stat_bonus(&stats, ARMORED_AC_STAT)),
+            None => rules::UNARMORED_ARMOR_CLASS.saturating_add(dexterity),
+        };
+        armor_class = armor_class
+            .saturating_add(base_armor_class)
+            .saturating_add(shield)
+            .saturating_add(stat_bonus(&stats, AC_STAT));
+
+        // The first level of the starting class, the one not taken by
+        // multiclassing, gets the most from its Hit Die
+        let classes = value.classes();
+        let starting = classes
+            .iter()
+            .find(|class| !class.multiclass)
+            .or(classes.first())
+            .map(|class| class.id.as_str());
+        let constitution = ability_scores.constitution.modifier();
+        let hit_dice: Vec<HitDice> = classes
+            .iter()
+            .filter_map(|class| {
+                let (metadata, _) = library.class(&class.id)?;
+                library.hit_die(&class.id).map(|die| HitDice {
+                    class: class.id.clone(),
+                    name: metadata.name.clone(),
+                    die,
+                    count: class.level,
+                })
+            })
+            .collect();
+        let hit_points = hit_dice
+            .iter()
+            .flat_map(|hd| {
+                let first = Some(hd.class.as_str()) == starting;
+                (0..hd.count).map(move |i| (hd.die.sides(), first && i == 0))
+            })
+            .map(|(sides, first)| rules::level_hit_points(sides, constitution, first))
+            .sum::<i32>()
+            + i32::from(stat_bonus(&stats, HP_STAT))
+            + i32::from(stat_bonus(&stats, HP_LEVEL_STAT)) * i32::from(level);
 
-        Ok(Self {
+        // Items inside of containers can't be equipped or attuned to
+        let stowed = value.spec.inventory.stowed_items();
+        let items = value
+            .spec
+            .inventory
+            .all_items()
+            .into_iter()
+            .filter_map(|carried| {
+                let in_use = !stowed.iter().any(|item| ::std::ptr::eq(*item, carried));
+                library.item(&carried.id).map(|item| CarriedItem {
+                    id: item.metadata.id.clone(),
+                    name: item.metadata.name.clone(),
+                    category: item.spec.kind.category(),
+                    quantity: carried.quantity,
+                    weight: item.spec.weight,
+                    equipped: carried.equipped && in_use,
+                    attuned: carried.attuned && in_use,
+                })
+            })
+            .collect();
+
+        let mut sheet = Self {
+            name: value.metadata.name.clone(),
             level,
+            experience,
+            experience_to_next_level,
+            proficiency_bonus: rules::proficiency_bonus(level),
             armor_class,
             languages,
             size,
             ability_scores,
+            skills: BTreeMap::new(),
             proficiencies,
-        })
+            stats,
+            senses,
+            speeds,
+            attacks: vec![],
+            carrying,
+            hit_points,
+            hit_dice,
+            features,
+            spells,
+            items,
+        };
+
+        sheet.skills = Skill::ALL
+            .iter()
+            .map(|&skill| (skill, sheet.skill_check(skill)))
+            .collect();
+
+        sheet.senses.passive_perception =
+            rules::passive_score(sheet.skill_modifier(Skill::Perception));
+
+        sheet.attacks = equipped
+            .iter()
+            .filter_map(|(_, item)| match &item.spec.kind {
+                ItemKind::Weapon(weapon) => Some(sheet.attack(item, weapon)),
+                _ => None,
+            })
+            .collect();
+
+        sheet
     }
 }
 
@@ -186,6 +977,37 @@ mod tests {
         }
     */
 
+    /// Returns a Character with `abilities`, from strength to charisma, and the
+    /// rest of its spec from `spec`
+    fn character(abilities: [i8; 6], spec: &str) -> Character {
+        let [strength, dexterity, constitution, intelligence, wisdom, charisma] = abilities;
+
+        serde_yaml::from_str(&format!(
+            "
+metadata:
+  id: uuid-lmao-lol
+  name: foobar
+spec:
+  abilities:
+    strength: {}
+    dexterity: {}
+    constitution: {}
+    intelligence: {}
+    wisdom: {}
+    charisma: {}
+{}",
+            strength, dexterity, constitution, intelligence, wisdom, charisma, spec
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_ability_roll_modifier_rounds_down() {
+        assert_eq!(AbilityRoll { base: 9, mods: 0 }.modifier(), -1);
+        assert_eq!(AbilityRoll { base: 8, mods: 3 }.modifier(), 0);
+        assert_eq!(AbilityRoll { base: 20, mods: 3 }.modifier(), 6);
+    }
+
     #[test]
     fn test_ability_scores_add_to_mod() {
         let mut scores = AbilityScores {
@@ -197,12 +1019,12 @@ mod tests {
             charisma: AbilityRoll { base: 13, mods: -1 },
         };
 
-        scores.add_to_mod("strength", 1);
-        scores.add_to_mod("dexterity", 0);
-        scores.add_to_mod("constitution", -1);
-        scores.add_to_mod("intelligence", 2);
-        scores.add_to_mod("wisdom", 2);
-        scores.add_to_mod("charisma", 1);
+        scores.add_to_mod(Ability::Strength, 1);
+        scores.add_to_mod(Ability::Dexterity, 0);
+        scores.add_to_mod(Ability::Constitution, -1);
+        scores.add_to_mod(Ability::Intelligence, 2);
+        scores.add_to_mod(Ability::Wisdom, 2);
+        scores.add_to_mod(Ability::Charisma, 1);
 
         assert_eq!(scores.strength.mods, 4);
         assert_eq!(scores.dexterity.mods, 1);
@@ -231,6 +1053,11 @@ mod tests {
                     wisdom: 10,
                     charisma: 13,
                 },
+                generation: Default::default(),
+                experience: 0,
+                options: Default::default(),
+                inventory: Default::default(),
+                currency: Default::default(),
                 assets: character::CharacterAssets {
                     class: CharacterClassSpec::Single(CharacterClass {
                         id: String::from("fighter"),
@@ -238,13 +1065,14 @@ mod tests {
                         multiclass: false,
                         grants: vec![
                             CharacterAssetGrant::ASI(ASI::Ability(
-                                String::from("strength"),
-                                Some(String::from("dexterity")),
+                                Ability::Strength,
+                                Some(Ability::Dexterity),
                             )),
                             CharacterAssetGrant::ASI(ASI::Feat {
                                 id: String::from("this feat"),
+                                ability: None,
                                 grants: vec![CharacterAssetGrant::AbilityScore {
-                                    id: String::from("constitution"),
+                                    id: Ability::Constitution,
                                     add: 1,
                                 }],
                             }),
@@ -257,7 +1085,7 @@ mod tests {
                                 id: String::from("medium"),
                             },
                             CharacterAssetGrant::AbilityScore {
-                                id: String::from("dexterity"),
+                                id: Ability::Dexterity,
                                 add: 2,
                             },
                             CharacterAssetGrant::Language {
@@ -274,12 +1102,17 @@ mod tests {
                             },
                         ],
                     },
+                    background: None,
                 },
             },
         };
 
         let expected = CharacterSheet {
+            name: String::from("foobar"),
             level: 1,
+            experience: 0,
+            experience_to_next_level: Some(300),
+            proficiency_bonus: 2,
             armor_class: 16,
             size: String::from("medium"),
             languages: vec![String::from("common"), String::from("elvish")],
@@ -290,6 +1123,19 @@ mod tests {
                 description: None,
                 extra: Default::default(),
             }],
+            stats: BTreeMap::new(),
+            senses: Senses {
+                ranges: BTreeMap::new(),
+                passive_perception: 10,
+            },
+            speeds: BTreeMap::new(),
+            attacks: vec![],
+            carrying: Carrying::new(0.0, 10, "medium", Encumbrance::Standard),
+            hit_points: 0,
+            hit_dice: vec![],
+            features: vec![],
+            spells: vec![],
+            items: vec![],
             ability_scores: AbilityScores {
                 strength: AbilityRoll { base: 9, mods: 1 },
                 dexterity: AbilityRoll { base: 20, mods: 3 },
@@ -298,10 +1144,704 @@ mod tests {
                 wisdom: AbilityRoll { base: 10, mods: 0 },
                 charisma: AbilityRoll { base: 13, mods: 0 },
             },
+            skills: Skill::ALL
+                .iter()
+                .map(|&skill| {
+                    let modifier = match skill.ability() {
+                        Ability::Dexterity => 6,
+                        Ability::Charisma => 1,
+                        _ => 0,
+                    };
+                    (
+                        skill,
+                        SkillCheck {
+                            modifier,
+                            proficient: false,
+                        },
+                    )
+                })
+                .collect(),
         };
 
         assert_eq!(expected, CharacterSheet::try_from(ch).unwrap());
     }
+
+    #[test]
+    fn test_character_sheet_feature_option_stats() {
+        let mut library = Library::new();
+        library
+            .add_yaml_str(
+                "
+type: feature
+metadata:
+  id: fighting-style
+  name: Fighting Style
+spec:
+  options:
+    - metadata:
+        id: archery
+        name: Archery
+      spec:
+        stat:
+          ranged.attack: 2
+    - metadata:
+        id: defense
+        name: Defense
+      spec:
+        stat:
+          armored.ac: 1
+",
+            )
+            .unwrap();
+
+        let ch = character(
+            [9, 18, 11, 11, 10, 13],
+            "
+  class:
+    id: fighter
+    level: 1
+    grants:
+      - type: feature
+        id: fighting-style
+        option: archery
+  race:
+    id: elf
+    grants:
+      - type: stat
+        id: ranged.attack
+        value: 1
+",
+        );
+
+        let sheet = CharacterSheet::build(&ch, &library);
+
+        assert_eq!(
+            BTreeMap::from([(String::from("ranged.attack"), Stat::Number(3))]),
+            sheet.stats
+        );
+    }
+
+    #[test]
+    fn test_character_sheet_senses() {
+        let ch = character(
+            [9, 18, 11, 11, 14, 13],
+            "
+  class:
+    id: fighter
+    level: 5
+    grants:
+      - type: proficiency
+        id: skill-perception
+      - type: vision
+        id: blindsight
+        range: 10ft
+  race:
+    id: elf
+    grants:
+      - type: vision
+        id: darkvision
+        range: 60ft
+      - type: trait
+        id: superior-darkvision
+        grants:
+          - type: vision
+            id: darkvision
+            range: 120ft
+      - type: vision
+        id: darkvision
+",
+        );
+
+        let sheet = CharacterSheet::build(&ch, &Library::new());
+
+        assert_eq!(Some(Some(Distance(120))), sheet.senses().range("darkvision"));
+        assert_eq!(Some(Some(Distance(10))), sheet.senses().range("blindsight"));
+        assert_eq!(None, sheet.senses().range("tremorsense"));
+        // 10 + 2 from wisdom + 3 from proficiency
+        assert_eq!(15, sheet.senses().passive_perception());
+    }
+
+    #[test]
+    fn test_speeds() {
+        let grants: Vec<CharacterAssetGrant> = serde_yaml::from_str(
+            "
+- {type: speed, id: walk, set: 25ft}
+- {type: speed, id: walk, set: 30ft}
+- {type: speed, id: walk, bonus: 10ft}
+- {type: speed, id: climb, equal-to: walk}
+- {type: speed, id: swim, bonus: 10ft}
+- {type: speed, id: fly, set: 50}
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            BTreeMap::from([
+                (Movement::Walk, Distance(40)),
+                (Movement::Fly, Distance(50)),
+                (Movement::Climb, Distance(40)),
+            ]),
+            speeds(&grants, 0)
+        );
+        assert_eq!(
+            BTreeMap::from([
+                (Movement::Walk, Distance(30)),
+                (Movement::Fly, Distance(40)),
+                (Movement::Climb, Distance(30)),
+            ]),
+            speeds(&grants, rules::HEAVY_ARMOR_SPEED_PENALTY)
+        );
+
+        let grants: Vec<CharacterAssetGrant> = serde_yaml::from_str(
+            "
+- {type: speed, id: walk, set: 30ft}
+- {type: speed, id: walk, bonus: 65535ft}
+",
+        )
+        .unwrap();
+        assert_eq!(
+            BTreeMap::from([(Movement::Walk, Distance(u16::MAX))]),
+            speeds(&grants, 0)
+        );
+    }
+
+    #[test]
+    fn test_character_sheet_background_from_library() {
+        let library = Library::load(::std::path::Path::new("../../data/background")).unwrap();
+
+        let ch = character(
+            [9, 18, 11, 11, 10, 13],
+            "
+  class:
+    id: fighter
+    level: 1
+    grants: []
+  race:
+    id: elf
+    grants: []
+  background:
+    id: acolyte
+    grants:
+      - type: language
+        id: dwarvish
+      - type: language
+        id: giant
+",
+        );
+
+        let sheet = CharacterSheet::build(&ch, &library);
+
+        assert_eq!(vec!["dwarvish", "giant"], sheet.languages);
+        assert!(sheet.is_proficient(&Skill::Insight.proficiency()));
+        assert!(sheet.is_proficient(&Skill::Religion.proficiency()));
+        assert!(!sheet.is_proficient(&Skill::Perception.proficiency()));
+    }
+
+    #[test]
+    fn test_character_sheet_feat_from_library() {
+        let mut library = Library::new();
+        library
+            .add_yaml_str(
+                "
+type: feat
+metadata:
+  id: heavily-armored
+  name: Heavily Armored
+spec:
+  ability: strength
+  grant:
+    - type: proficiency
+      id: armor-heavy
+",
+            )
+            .unwrap();
+
+        let ch = character(
+            [9, 18, 11, 11, 10, 13],
+            "
+  class:
+    id: fighter
+    level: 4
+    grants:
+      - type: asi
+        feat:
+          id: heavily-armored
+          ability: strength
+  race:
+    id: elf
+    grants: []
+",
+        );
+
+        let sheet = CharacterSheet::build(&ch, &library);
+
+        assert_eq!(10, sheet.ability_scores.strength.total());
+        assert!(sheet.is_proficient("armor-heavy"));
+    }
+
+    #[test]
+    fn test_character_sheet_armor() {
+        let mut library = Library::new();
+        library
+            .add_yaml_str(
+                "
+type: item
+metadata:
+  id: half-plate
+  name: Half Plate
+spec:
+  kind: armor
+  category: medium
+  ac: 15
+---
+type: item
+metadata:
+  id: chain-mail
+  name: Chain Mail
+spec:
+  kind: armor
+  category: heavy
+  ac: 16
+  strength: 13
+---
+type: item
+metadata:
+  id: shield
+  name: Shield
+spec:
+  kind: shield
+  ac: 2
+",
+            )
+            .unwrap();
+
+        let wearing = |items: &str| {
+            let ch = character(
+                [9, 18, 11, 11, 10, 13],
+                &format!(
+                    "
+  inventory:
+    items: {}
+  class:
+    id: fighter
+    level: 1
+    grants:
+      - type: stat
+        id: armored.ac
+        value: 1
+  race:
+    id: elf
+    grants:
+      - type: speed
+        id: walk
+        set: 30ft
+",
+                    items
+                ),
+            );
+
+            CharacterSheet::build(&ch, &library)
+        };
+
+        let unarmored = wearing("[{id: half-plate}]");
+        assert_eq!(14, unarmored.armor_class);
+        assert_eq!(Some(&Distance(30)), unarmored.speeds().get(&Movement::Walk));
+
+        let medium = wearing("[{id: half-plate, equipped: true}, {id: shield, equipped: true}]");
+        assert_eq!(15 + 2 + 1 + 2, medium.armor_class);
+
+        let heavy = wearing("[{id: chain-mail, equipped: true}]");
+        assert_eq!(16 + 1, heavy.armor_class);
+        assert_eq!(Some(&Distance(20)), heavy.speeds().get(&Movement::Walk));
+
+        // Armor in a backpack isn't worn
+        let stowed = wearing("[{id: backpack, contents: [{id: chain-mail, equipped: true}]}]");
+        assert_eq!(14, stowed.armor_class);
+        assert!(!stowed.items().iter().any(|item| item.equipped));
+    }
+
+    #[test]
+    fn test_character_sheet_large_stats_saturate() {
+        let ch = character(
+            [9, 18, 11, 11, 10, 13],
+            "
+  class:
+    id: fighter
+    level: 1
+    grants:
+      - type: stat
+        id: ac
+        value: 200
+      - type: stat
+        id: attack
+        value: 100
+      - type: stat
+        id: melee.attack
+        value: 100
+  race:
+    id: elf
+    grants: []
+",
+        );
+        let sheet = CharacterSheet::build(&ch, &Library::new());
+
+        assert_eq!(i8::MAX, sheet.armor_class);
+        assert_eq!(i8::MAX, stat_bonus(&sheet.stats, "ac"));
+        assert_eq!(0, stat_bonus(&sheet.stats, "hp"));
+    }
+
+    #[test]
+    fn test_character_sheet_attacks() {
+        let mut library = Library::load(::std::path::Path::new("../../data/item")).unwrap();
+        library
+            .add_yaml_str(
+                "
+type: feature
+metadata:
+  id: fighting-style
+  name: Fighting Style
+spec:
+  options:
+    - metadata:
+        id: archery
+        name: Archery
+      spec:
+        stat:
+          ranged.attack: 2
+",
+            )
+            .unwrap();
+
+        let ch = character(
+            [16, 18, 11, 11, 10, 13],
+            "
+  inventory:
+    items:
+      - {id: longsword, equipped: true}
+      - {id: dagger, equipped: true}
+      - {id: longbow, equipped: true}
+      - {id: greatsword}
+      - {id: backpack, contents: [{id: greatsword, equipped: true}]}
+  class:
+    id: fighter
+    level: 5
+    grants:
+      - type: proficiency
+        id: weapon-martial
+      - type: feature
+        id: fighting-style
+        option: archery
+  race:
+    id: elf
+    grants: []
+",
+        );
+
+        let sheet = CharacterSheet::build(&ch, &library);
+        let attack = |id: &str| sheet.attacks().iter().find(|a| a.id == id).unwrap();
+
+        assert_eq!(3, sheet.attacks().len());
+
+        // Strength, proficient
+        let longsword = attack("longsword");
+        assert_eq!(3 + 3, longsword.bonus);
+        assert_eq!("1d8+3", longsword.damage.to_string());
+        assert_eq!(
+            Some(String::from("1d10+3")),
+            longsword.two_handed_damage.as_ref().map(|d| d.to_string())
+        );
+        assert_eq!(None, longsword.range);
+
+        // Finesse uses Dexterity, and isn't proficient without weapon-simple
+        let dagger = attack("dagger");
+        assert_eq!(4, dagger.bonus);
+        assert_eq!("1d4+4", dagger.damage.to_string());
+        assert_eq!(Some(Distance(20)), dagger.range.map(|r| r.normal));
+
+        // Dexterity, proficient and Archery
+        let longbow = attack("longbow");
+        assert_eq!(4 + 3 + 2, longbow.bonus);
+        assert_eq!("1d8+4", longbow.damage.to_string());
+        assert!(longbow.two_handed);
+        assert_eq!("piercing", longbow.damage_type);
+    }
+
+    #[test]
+    fn test_character_sheet_carrying() {
+        let library = Library::load(::std::path::Path::new("../../data/item")).unwrap();
+        let carrying = |encumbrance: &str, size: &str| {
+            character(
+                [8, 10, 11, 11, 10, 13],
+                &format!(
+                    "
+  options:
+    encumbrance: {}
+  inventory:
+    items:
+      - {{id: chain-mail}}
+      - {{id: backpack, contents: [{{id: rations, quantity: 10}}]}}
+  class:
+    id: fighter
+    level: 1
+    grants: []
+  race:
+    id: elf
+    grants:
+      - type: size
+        id: {}
+      - type: speed
+        id: walk
+        set: 30ft
+",
+                    encumbrance, size
+                ),
+            )
+        };
+
+        let sheet = CharacterSheet::build(&carrying("standard", "medium"), &library);
+        assert_eq!(55.0 + 5.0 + 2.0 * 10.0, sheet.carrying().weight());
+        assert_eq!(120.0, sheet.carrying().capacity());
+        assert_eq!(240.0, sheet.carrying().push_drag_lift());
+        assert_eq!(
+            EncumbranceLevel::Unencumbered,
+            sheet.carrying().encumbrance()
+        );
+        assert_eq!(Some(&Distance(30)), sheet.speeds().get(&Movement::Walk));
+
+        // 80lb is over 5 times Strength, but not over 10 times
+        let sheet = CharacterSheet::build(&carrying("variant", "medium"), &library);
+        assert_eq!(EncumbranceLevel::Encumbered, sheet.carrying().encumbrance());
+        assert_eq!(Some(&Distance(20)), sheet.speeds().get(&Movement::Walk));
+
+        let sheet = CharacterSheet::build(&carrying("variant", "tiny"), &library);
+        assert_eq!(60.0, sheet.carrying().capacity());
+        assert!(sheet.carrying().is_over_capacity());
+        assert_eq!(
+            EncumbranceLevel::HeavilyEncumbered,
+            sheet.carrying().encumbrance()
+        );
+        assert_eq!(Some(&Distance(10)), sheet.speeds().get(&Movement::Walk));
+
+        let sheet = CharacterSheet::build(&carrying("variant", "large"), &library);
+        assert_eq!(240.0, sheet.carrying().capacity());
+        assert_eq!(
+            EncumbranceLevel::Unencumbered,
+            sheet.carrying().encumbrance()
+        );
+    }
+
+    #[test]
+    fn test_character_sheet_hit_points() {
+        let mut library =
+            Library::load(::std::path::Path::new("../../data/class/fighter.yml")).unwrap();
+        library
+            .add_yaml_str(
+                "
+type: class
+metadata:
+  id: wizard
+  name: Wizard
+spec:
+  set:
+    hit-dice: d6
+  grant: []
+  select: []
+---
+type: feature
+metadata:
+  id: tough
+  name: Tough
+spec:
+  stat:
+    hp.level: 2
+",
+            )
+            .unwrap();
+
+        let mut ch = character(
+            [16, 10, 14, 11, 10, 13],
+            "
+  class:
+    - id: fighter
+      level: 3
+      grants:
+        - type: feature
+          id: tough
+    - id: wizard
+      level: 2
+      multiclass: true
+      grants: []
+  race:
+    id: elf
+    grants: []
+",
+        );
+
+        let sheet = CharacterSheet::build(&ch, &library);
+
+        assert_eq!(
+            vec![(Die::D10, 3), (Die::D6, 2)],
+            sheet
+                .hit_dice()
+                .iter()
+                .map(|hd| (hd.die, hd.count))
+                .collect::<Vec<_>>()
+        );
+        // 10 + 6 + 6 from fighter, 4 + 4 from wizard, and 2 from constitution and 2 from tough a level
+        assert_eq!(30 + 5 * 2 + 5 * 2, sheet.hit_points());
+        assert_eq!("Tough", sheet.features()[0].name);
+
+        // Fighter is still the starting class when listed after the multiclass
+        if let CharacterClassSpec::Multi(classes) = &mut ch.spec.assets.class {
+            classes.reverse();
+        }
+        let sheet = CharacterSheet::build(&ch, &library);
+        assert_eq!(30 + 5 * 2 + 5 * 2, sheet.hit_points());
+    }
+
+    #[test]
+    fn test_character_sheet_serde_round_trip() {
+        let mut library = Library::load(::std::path::Path::new("../../data/item")).unwrap();
+        library
+            .add_path(::std::path::Path::new("../../data/class/fighter.yml"))
+            .unwrap();
+        library
+            .add_path(::std::path::Path::new("../../data/trait"))
+            .unwrap();
+
+        let ch = character(
+            [16, 12, 14, 8, 10, 13],
+            "
+  inventory:
+    items:
+      - {id: longsword, equipped: true}
+      - {id: rations, quantity: 3}
+  class:
+    id: fighter
+    level: 3
+    grants:
+      - type: proficiency
+        id: skill-athletics
+  race:
+    id: elf
+    grants:
+      - type: abilityscore
+        id: dexterity
+        add: 2
+      - type: vision
+        id: darkvision
+        range: 60ft
+      - type: speed
+        id: walk
+        set: 30ft
+      - type: trait
+        id: fey-step
+        grants: []
+",
+        );
+
+        let sheet = CharacterSheet::build(&ch, &library);
+        let json = serde_json::to_value(&sheet).unwrap();
+
+        assert_eq!(
+            serde_json::json!({ "base": 12, "mods": 2, "total": 14, "modifier": 2 }),
+            json["ability_scores"]["dexterity"]
+        );
+        assert_eq!(
+            serde_json::json!({ "modifier": 5, "proficient": true }),
+            json["skills"]["athletics"]
+        );
+        assert_eq!(2, json["proficiency_bonus"]);
+        assert_eq!("1d8+3", json["attacks"][0]["damage"]);
+
+        let round_trip: CharacterSheet = serde_json::from_value(json).unwrap();
+        assert_eq!(sheet, round_trip);
+    }
+
+    #[test]
+    fn test_character_sheet_race_from_library() {
+        let mut library = Library::new();
+        library
+            .add_yaml_str(
+                "
+type: race
+metadata:
+  id: elf
+  name: Elf
+spec:
+  size: medium
+  speed:
+    walk: 30ft
+  ability:
+    dexterity: 2
+  grant:
+    - type: language
+      id: common
+    - type: language
+      id: elvish
+    - type: trait
+      id: keen-senses
+---
+type: sub-race
+metadata:
+  id: high-elf
+  name: High Elf
+spec:
+  race: elf
+  ability:
+    intelligence: 1
+---
+type: trait
+metadata:
+  id: keen-senses
+  name: Keen Senses
+spec:
+  grant:
+    - type: proficiency
+      id: perception
+---
+type: proficiency
+metadata:
+  id: perception
+  name: Perception
+",
+            )
+            .unwrap();
+
+        let ch = character(
+            [9, 18, 11, 11, 10, 13],
+            "
+  class:
+    id: fighter
+    level: 1
+    grants: []
+  race:
+    id: elf
+    grants:
+      - type: language
+        id: common
+      - type: sub-race
+        id: high-elf
+        grants: []
+",
+        );
+
+        let sheet = CharacterSheet::build(&ch, &library);
+
+        assert_eq!("medium", sheet.size);
+        assert_eq!(Some(&Distance(30)), sheet.speeds().get(&Movement::Walk));
+        assert_eq!(vec!["common", "elvish"], sheet.languages);
+        assert_eq!(20, sheet.ability_scores.dexterity.total());
+        assert_eq!(12, sheet.ability_scores.intelligence.total());
+        assert_eq!(
+            vec![String::from("Perception")],
+            sheet
+                .proficiencies
+                .iter()
+                .map(|p| p.name.clone())
+                .collect::<Vec<_>>()
+        );
+    }
 }
 
 /*
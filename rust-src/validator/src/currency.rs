@@ -0,0 +1,471 @@
+//! Money, and what things cost.
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+
+use crate::assets::item::Item;
+
+/// Error for Currency operations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CurrencyError {
+    /// There isn't enough money to pay
+    InsufficientFunds {
+        /// What had to be paid, in copper pieces
+        cost: u64,
+        /// What there is, in copper pieces
+        available: u64,
+    },
+    /// The Item has no cost to buy or sell it for
+    NoCost(String),
+    /// The total is too large to count in copper pieces
+    Overflow,
+}
+
+impl fmt::Display for CurrencyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InsufficientFunds { cost, available } => write!(
+                f,
+                "{}cp is needed but only {}cp is available",
+                cost, available
+            ),
+            Self::NoCost(id) => write!(f, "the item `{}` has no cost", id),
+            Self::Overflow => write!(f, "the total is too large to count"),
+        }
+    }
+}
+
+impl ::std::error::Error for CurrencyError {}
+
+/// A coin, each worth a number of copper pieces
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
+pub enum Denomination {
+    /// Copper Piece
+    Copper,
+    /// Silver Piece
+    Silver,
+    /// Electrum Piece
+    Electrum,
+    /// Gold Piece
+    Gold,
+    /// Platinum Piece
+    Platinum,
+}
+
+impl Denomination {
+    /// Every Denomination, from least to most valuable
+    pub const ALL: [Denomination; 5] = [
+        Denomination::Copper,
+        Denomination::Silver,
+        Denomination::Electrum,
+        Denomination::Gold,
+        Denomination::Platinum,
+    ];
+
+    /// Returns how many copper pieces one of this coin is worth
+    pub fn copper(&self) -> u64 {
+        match self {
+            Denomination::Copper => 1,
+            Denomination::Silver => 10,
+            Denomination::Electrum => 50,
+            Denomination::Gold => 100,
+            Denomination::Platinum => 1_000,
+        }
+    }
+
+    /// Returns the abbreviation of the coin, such as `gp`
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Denomination::Copper => "cp",
+            Denomination::Silver => "sp",
+            Denomination::Electrum => "ep",
+            Denomination::Gold => "gp",
+            Denomination::Platinum => "pp",
+        }
+    }
+}
+
+/// Coins a Character has
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Default, Clone, Copy, JsonSchema)]
+pub struct Currency {
+    /// Copper Pieces
+    #[serde(default)]
+    pub copper: u64,
+    /// Silver Pieces
+    #[serde(default)]
+    pub silver: u64,
+    /// Electrum Pieces
+    #[serde(default)]
+    pub electrum: u64,
+    /// Gold Pieces
+    #[serde(default)]
+    pub gold: u64,
+    /// Platinum Pieces
+    #[serde(default)]
+    pub platinum: u64,
+}
+
+impl Currency {
+    /// Returns the total value of every coin, in copper pieces
+    pub fn total(&self) -> Result<u64, CurrencyError> {
+        Denomination::ALL.iter().try_fold(0u64, |total, &d| {
+            self[d]
+                .checked_mul(d.copper())
+                .and_then(|copper| total.checked_add(copper))
+                .ok_or(CurrencyError::Overflow)
+        })
+    }
+
+    /// Returns the total value of every coin in `denomination`, rounded down
+    pub fn total_in(&self, denomination: Denomination) -> Result<u64, CurrencyError> {
+        Ok(self.total()? / denomination.copper())
+    }
+
+    /// Returns the fewest coins worth `copper` copper pieces.
+    ///
+    /// Electrum is never used, as most tables don't.
+    pub fn from_copper(mut copper: u64) -> Self {
+        let mut currency = Self::default();
+
+        for d in Denomination::ALL.iter().rev() {
+            if *d == Denomination::Electrum {
+                continue;
+            }
+            currency[*d] = copper / d.copper();
+            copper %= d.copper();
+        }
+
+        currency
+    }
+
+    /// Returns the coins of both purses, or [`None`] if there are too many of a coin to count
+    pub fn checked_add(&self, other: Self) -> Option<Self> {
+        let mut sum = *self;
+        for d in Denomination::ALL {
+            sum[d] = sum[d].checked_add(other[d])?;
+        }
+
+        Some(sum)
+    }
+
+    /// Exchanges every coin for the fewest coins of the same total value
+    pub fn normalize(&mut self) -> Result<(), CurrencyError> {
+        *self = Self::from_copper(self.total()?);
+
+        Ok(())
+    }
+
+    /// Pays `cost`, spending the least valuable coins first and getting
+    /// change back as the fewest coins
+    pub fn pay(&mut self, cost: Cost) -> Result<(), CurrencyError> {
+        let cost = cost.copper()?;
+        let available = self.total()?;
+
+        if cost > available {
+            return Err(CurrencyError::InsufficientFunds { cost, available });
+        }
+
+        let mut purse = *self;
+        let mut paid = 0u64;
+        for d in Denomination::ALL {
+            if paid >= cost {
+                break;
+            }
+            let coins = purse[d].min((cost - paid).div_ceil(d.copper()));
+            purse[d] -= coins;
+            paid = coins
+                .checked_mul(d.copper())
+                .and_then(|copper| paid.checked_add(copper))
+                .ok_or(CurrencyError::Overflow)?;
+        }
+
+        *self = purse
+            .checked_add(Self::from_copper(paid - cost))
+            .ok_or(CurrencyError::Overflow)?;
+
+        Ok(())
+    }
+
+    /// Receives `cost`
+    pub fn receive(&mut self, cost: Cost) -> Result<(), CurrencyError> {
+        let coins = &mut self[cost.denomination];
+        *coins = coins
+            .checked_add(cost.amount)
+            .ok_or(CurrencyError::Overflow)?;
+
+        Ok(())
+    }
+
+    /// Buys `quantity` of `item` at its cost
+    pub fn buy(&mut self, item: &Item, quantity: u32) -> Result<(), CurrencyError> {
+        self.pay(Cost {
+            amount: item_total(item, quantity)?,
+            denomination: Denomination::Copper,
+        })
+    }
+
+    /// Sells `quantity` of `item` at half its cost, as Items are usually sold for,
+    /// getting the fewest coins for it
+    pub fn sell(&mut self, item: &Item, quantity: u32) -> Result<(), CurrencyError> {
+        *self = self
+            .checked_add(Self::from_copper(item_total(item, quantity)? / 2))
+            .ok_or(CurrencyError::Overflow)?;
+
+        Ok(())
+    }
+}
+
+/// Returns what `quantity` of `item` cost, in copper pieces
+fn item_total(item: &Item, quantity: u32) -> Result<u64, CurrencyError> {
+    let cost = item
+        .spec
+        .cost
+        .ok_or_else(|| CurrencyError::NoCost(item.metadata.id.clone()))?;
+
+    cost.copper()?
+        .checked_mul(u64::from(quantity))
+        .ok_or(CurrencyError::Overflow)
+}
+
+impl Index<Denomination> for Currency {
+    type Output = u64;
+
+    fn index(&self, denomination: Denomination) -> &Self::Output {
+        match denomination {
+            Denomination::Copper => &self.copper,
+            Denomination::Silver => &self.silver,
+            Denomination::Electrum => &self.electrum,
+            Denomination::Gold => &self.gold,
+            Denomination::Platinum => &self.platinum,
+        }
+    }
+}
+
+impl IndexMut<Denomination> for Currency {
+    fn index_mut(&mut self, denomination: Denomination) -> &mut Self::Output {
+        match denomination {
+            Denomination::Copper => &mut self.copper,
+            Denomination::Silver => &mut self.silver,
+            Denomination::Electrum => &mut self.electrum,
+            Denomination::Gold => &mut self.gold,
+            Denomination::Platinum => &mut self.platinum,
+        }
+    }
+}
+
+/// What something costs, such as `15gp`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Cost {
+    /// Number of coins
+    pub amount: u64,
+    /// The coin
+    pub denomination: Denomination,
+}
+
+impl Cost {
+    /// Returns what this costs in copper pieces
+    pub fn copper(&self) -> Result<u64, CurrencyError> {
+        self.amount
+            .checked_mul(self.denomination.copper())
+            .ok_or(CurrencyError::Overflow)
+    }
+}
+
+impl FromStr for Cost {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (amount, denomination) = Denomination::ALL
+            .iter()
+            .find_map(|d| s.strip_suffix(d.abbreviation()).map(|amount| (amount, *d)))
+            .ok_or_else(|| format!("`{}` has no denomination such as `gp`", s))?;
+
+        let amount = amount
+            .trim()
+            .parse()
+            .map_err(|_| format!("`{}` is not a number of coins", amount.trim()))?;
+
+        Ok(Cost {
+            amount,
+            denomination,
+        })
+    }
+}
+
+impl fmt::Display for Cost {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.amount, self.denomination.abbreviation())
+    }
+}
+
+impl Serialize for Cost {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl JsonSchema for Cost {
+    fn schema_name() -> String {
+        String::from("Cost")
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        crate::schema::text(
+            "What something costs",
+            Some(r"^\s*[0-9]+\s*(cp|sp|ep|gp|pp)$"),
+            &["15gp", "5 sp"],
+        )
+        .into()
+    }
+}
+
+impl<'de> Deserialize<'de> for Cost {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cost_from_str() {
+        assert_eq!(
+            Ok(Cost {
+                amount: 15,
+                denomination: Denomination::Gold,
+            }),
+            "15gp".parse()
+        );
+        assert_eq!(Ok(5), "5 sp".parse::<Cost>().map(|c| c.amount));
+        assert_eq!(Ok(Ok(2_000)), "2pp".parse::<Cost>().map(|c| c.copper()));
+        assert_eq!(
+            Err(CurrencyError::Overflow),
+            "18446744073709551615pp".parse::<Cost>().unwrap().copper()
+        );
+        assert!("15".parse::<Cost>().is_err());
+        assert!("lots gp".parse::<Cost>().is_err());
+    }
+
+    fn coins(copper: u64, silver: u64, electrum: u64, gold: u64, platinum: u64) -> Currency {
+        Currency {
+            copper,
+            silver,
+            electrum,
+            gold,
+            platinum,
+        }
+    }
+
+    #[test]
+    fn test_currency_total() {
+        let purse = coins(5, 3, 1, 2, 1);
+
+        assert_eq!(Ok(5 + 30 + 50 + 200 + 1_000), purse.total());
+        assert_eq!(Ok(12), purse.total_in(Denomination::Gold));
+        assert_eq!(Ok(128), purse.total_in(Denomination::Silver));
+
+        let mut hoard = coins(0, 0, 0, 0, u64::MAX);
+        assert_eq!(Err(CurrencyError::Overflow), hoard.total());
+        assert_eq!(Err(CurrencyError::Overflow), hoard.normalize());
+        assert_eq!(coins(0, 0, 0, 0, u64::MAX), hoard);
+    }
+
+    #[test]
+    fn test_currency_normalize() {
+        let mut purse = coins(125, 30, 3, 0, 0);
+        purse.normalize().unwrap();
+
+        assert_eq!(coins(5, 7, 0, 5, 0), purse);
+    }
+
+    #[test]
+    fn test_currency_pay() {
+        let mut purse = coins(3, 0, 0, 2, 0);
+        purse.pay("15sp".parse().unwrap()).unwrap();
+
+        // 3cp and both gold pieces are spent, with 53cp of change
+        assert_eq!(coins(3, 5, 0, 0, 0), purse);
+
+        assert_eq!(
+            Err(CurrencyError::InsufficientFunds {
+                cost: 100,
+                available: 53,
+            }),
+            purse.pay("1gp".parse().unwrap())
+        );
+        assert_eq!(coins(3, 5, 0, 0, 0), purse);
+
+        assert_eq!(
+            Err(CurrencyError::Overflow),
+            purse.pay("18446744073709551615pp".parse().unwrap())
+        );
+        assert_eq!(coins(3, 5, 0, 0, 0), purse);
+    }
+
+    #[test]
+    fn test_currency_receive() {
+        let mut purse = coins(3, 0, 0, u64::MAX - 1, 0);
+        purse.receive("2sp".parse().unwrap()).unwrap();
+        assert_eq!(coins(3, 2, 0, u64::MAX - 1, 0), purse);
+
+        assert_eq!(
+            Err(CurrencyError::Overflow),
+            purse.receive("2gp".parse().unwrap())
+        );
+        assert_eq!(coins(3, 2, 0, u64::MAX - 1, 0), purse);
+    }
+
+    #[test]
+    fn test_currency_buy_and_sell() {
+        let longsword: Item = serde_yaml::from_str(
+            "
+metadata:
+  id: longsword
+  name: Longsword
+spec:
+  kind: weapon
+  category: martial
+  damage: 1d8
+  damage-type: slashing
+  cost: 15gp
+",
+        )
+        .unwrap();
+        let mut purse = coins(0, 0, 0, 45, 0);
+
+        purse.buy(&longsword, 2).unwrap();
+        assert_eq!(Ok(1_500), purse.total());
+        assert!(purse.buy(&longsword, 1).is_ok());
+        assert_eq!(
+            Err(CurrencyError::InsufficientFunds {
+                cost: 1_500,
+                available: 0,
+            }),
+            purse.buy(&longsword, 1)
+        );
+
+        purse.sell(&longsword, 1).unwrap();
+        assert_eq!(coins(0, 5, 0, 7, 0), purse);
+
+        // Only the proceeds are added, the other coins are left as they are
+        let mut purse = coins(0, 0, 10, 0, 0);
+        purse.sell(&longsword, 1).unwrap();
+        assert_eq!(coins(0, 5, 10, 7, 0), purse);
+
+        let mut castle = longsword;
+        castle.spec.cost = Some("18446744073709551615pp".parse().unwrap());
+        assert_eq!(Err(CurrencyError::Overflow), purse.buy(&castle, 1));
+        assert_eq!(Err(CurrencyError::Overflow), purse.sell(&castle, 2));
+        assert_eq!(coins(0, 5, 10, 7, 0), purse);
+    }
+}
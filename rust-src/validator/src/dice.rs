@@ -0,0 +1,665 @@
+//! Dice expressions, such as `2d6+3`, `1d20+str+prof` or `4d6kh3`.
+use std::fmt;
+use std::str::FromStr;
+
+use rand::Rng;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::assets::character::Ability;
+
+/// Error for Dice Expressions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiceError {
+    /// The expression could not be parsed
+    Parse(String),
+    /// The expression has a symbolic term that has not been resolved
+    Unresolved(Symbol),
+    /// The total does not fit in an `i32`
+    Overflow,
+}
+
+impl fmt::Display for DiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Parse(message) => write!(f, "invalid dice expression: {}", message),
+            Self::Unresolved(symbol) => write!(f, "`{}` has not been resolved", symbol),
+            Self::Overflow => write!(f, "the total of the dice expression is too large"),
+        }
+    }
+}
+
+impl ::std::error::Error for DiceError {}
+
+/// Most dice a single term can roll
+pub const MAX_DICE: u32 = 1_000;
+
+/// Most sides a die can have
+pub const MAX_SIDES: u32 = 1_000;
+
+/// Which dice of a roll are kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keep {
+    /// Keep the highest n dice
+    Highest(u32),
+    /// Keep the lowest n dice
+    Lowest(u32),
+}
+
+/// A value that is only known once the expression is resolved against a Character
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symbol {
+    /// An Ability's modifier
+    Ability(Ability),
+    /// The proficiency bonus
+    Proficiency,
+    /// The Character's level
+    Level,
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Ability(ability) => write!(f, "{}", &ability.name()[..3]),
+            Self::Proficiency => write!(f, "prof"),
+            Self::Level => write!(f, "level"),
+        }
+    }
+}
+
+impl FromStr for Symbol {
+    type Err = DiceError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "prof" | "proficiency" => return Ok(Self::Proficiency),
+            "level" => return Ok(Self::Level),
+            _ => {}
+        }
+
+        Ability::ALL
+            .into_iter()
+            .find(|a| a.name() == input || &a.name()[..3] == input)
+            .map(Self::Ability)
+            .ok_or_else(|| DiceError::Parse(format!("unknown term `{}`", input)))
+    }
+}
+
+/// A single term of a Dice Expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Term {
+    /// Dice to roll
+    Dice {
+        /// Number of dice
+        count: u32,
+        /// Number of sides on each die
+        sides: u32,
+        /// Which dice are kept, all if [`None`]
+        keep: Option<Keep>,
+    },
+    /// A plain number
+    Constant(i32),
+    /// A value to be resolved against a Character
+    Symbol(Symbol),
+}
+
+impl Term {
+    fn kept(count: u32, keep: Option<Keep>) -> u32 {
+        match keep {
+            Some(Keep::Highest(n)) | Some(Keep::Lowest(n)) => n.min(count),
+            None => count,
+        }
+    }
+
+    fn min(&self) -> Result<i32, DiceError> {
+        match *self {
+            Self::Dice { count, keep, .. } => Ok(Self::kept(count, keep) as i32),
+            Self::Constant(n) => Ok(n),
+            Self::Symbol(symbol) => Err(DiceError::Unresolved(symbol)),
+        }
+    }
+
+    fn max(&self) -> Result<i32, DiceError> {
+        match *self {
+            Self::Dice { count, sides, keep } => Ok((Self::kept(count, keep) * sides) as i32),
+            Self::Constant(n) => Ok(n),
+            Self::Symbol(symbol) => Err(DiceError::Unresolved(symbol)),
+        }
+    }
+
+    /// The sum of the kept dice is the sum, over every face value x, of how many
+    /// kept dice rolled at least x. The number of dice rolling at least x is
+    /// binomial, which gives an exact average without listing every outcome.
+    fn average(&self) -> Result<f64, DiceError> {
+        match *self {
+            Self::Dice {
+                count,
+                sides,
+                keep: None,
+            } => Ok(count as f64 * (sides as f64 + 1.0) / 2.0),
+            Self::Dice {
+                count,
+                sides,
+                keep: Some(keep),
+            } => {
+                let kept = Self::kept(count, Some(keep));
+                // How many dice rolling at least x are kept, when `b` of them do
+                let kept_at_least = |b: u32| match keep {
+                    Keep::Highest(_) => b.min(kept) as f64,
+                    Keep::Lowest(_) => b.saturating_sub(count - kept) as f64,
+                };
+
+                Ok((1..=sides)
+                    .map(|x| {
+                        let p = (sides - x + 1) as f64 / sides as f64;
+                        binomial_expectation(count, p, kept_at_least)
+                    })
+                    .sum())
+            }
+            Self::Constant(n) => Ok(n as f64),
+            Self::Symbol(symbol) => Err(DiceError::Unresolved(symbol)),
+        }
+    }
+
+    fn roll<R: Rng + ?Sized>(&self, rng: &mut R, rolls: &mut Vec<u32>) -> Result<i32, DiceError> {
+        match *self {
+            Self::Dice { count, sides, keep } => {
+                let mut dice: Vec<u32> = (0..count).map(|_| rng.gen_range(1..=sides)).collect();
+                rolls.extend(&dice);
+
+                dice.sort_unstable();
+                let kept = Self::kept(count, keep) as usize;
+                let kept = match keep {
+                    Some(Keep::Highest(_)) => &dice[dice.len() - kept..],
+                    _ => &dice[..kept],
+                };
+
+                i32::try_from(kept.iter().sum::<u32>()).map_err(|_| DiceError::Overflow)
+            }
+            Self::Constant(n) => Ok(n),
+            Self::Symbol(symbol) => Err(DiceError::Unresolved(symbol)),
+        }
+    }
+}
+
+/// Returns the expected value of `f(k)`, where `k` of `n` trials succeed and each has chance `p`
+///
+/// The chances are worked out in log space, as `p^n` underflows for large `n`.
+fn binomial_expectation<F: Fn(u32) -> f64>(n: u32, p: f64, f: F) -> f64 {
+    if p >= 1.0 {
+        return f(n);
+    }
+
+    let (ln_p, ln_q) = (p.ln(), (1.0 - p).ln());
+    let mut ln_choose = 0.0;
+
+    (0..=n)
+        .map(|k| {
+            if k > 0 {
+                ln_choose += ((n - k + 1) as f64).ln() - (k as f64).ln();
+            }
+            f(k) * (ln_choose + k as f64 * ln_p + (n - k) as f64 * ln_q).exp()
+        })
+        .sum()
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Dice { count, sides, keep } => {
+                write!(f, "{}d{}", count, sides)?;
+                match keep {
+                    Some(Keep::Highest(n)) => write!(f, "kh{}", n),
+                    Some(Keep::Lowest(n)) => write!(f, "kl{}", n),
+                    None => Ok(()),
+                }
+            }
+            Self::Constant(n) => write!(f, "{}", n),
+            Self::Symbol(symbol) => write!(f, "{}", symbol),
+        }
+    }
+}
+
+impl FromStr for Term {
+    type Err = DiceError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let number = |s: &str| {
+            s.parse::<u32>()
+                .map_err(|_| DiceError::Parse(format!("`{}` is not a number", s)))
+        };
+
+        if input.is_empty() {
+            return Err(DiceError::Parse(String::from("missing term")));
+        }
+
+        if input.chars().all(|c| c.is_ascii_digit()) {
+            return input
+                .parse()
+                .map(Self::Constant)
+                .map_err(|_| DiceError::Parse(format!("`{}` is too large", input)));
+        }
+
+        let Some((count, rest)) = input.split_once('d').filter(|(count, rest)| {
+            count.chars().all(|c| c.is_ascii_digit())
+                && rest.starts_with(|c: char| c.is_ascii_digit())
+        }) else {
+            return input.parse().map(Self::Symbol);
+        };
+
+        let count = if count.is_empty() { 1 } else { number(count)? };
+        let (sides, keep) = match rest.find('k') {
+            Some(i) => {
+                let keep = match &rest[i..] {
+                    k if k.starts_with("kh") => Keep::Highest(number(&k[2..])?),
+                    k if k.starts_with("kl") => Keep::Lowest(number(&k[2..])?),
+                    k => Keep::Highest(number(&k[1..])?),
+                };
+                (number(&rest[..i])?, Some(keep))
+            }
+            None => (number(rest)?, None),
+        };
+
+        if count == 0 || sides == 0 {
+            return Err(DiceError::Parse(format!("`{}` rolls no dice", input)));
+        }
+        if count > MAX_DICE || sides > MAX_SIDES {
+            return Err(DiceError::Parse(format!(
+                "`{}` rolls more than {}d{}",
+                input, MAX_DICE, MAX_SIDES
+            )));
+        }
+
+        Ok(Self::Dice { count, sides, keep })
+    }
+}
+
+/// Everything about a Character that the symbolic terms of a [`DiceExpression`] refer to
+pub trait DiceContext {
+    /// Returns the modifier of `ability`
+    fn ability_modifier(&self, ability: Ability) -> i32;
+    /// Returns the proficiency bonus
+    fn proficiency_bonus(&self) -> i32;
+    /// Returns the Character's level
+    fn level(&self) -> i32;
+}
+
+/// The result of rolling a [`DiceExpression`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Roll {
+    /// The total of the roll
+    pub total: i32,
+    /// Every die rolled, including dropped ones
+    pub dice: Vec<u32>,
+}
+
+/// A sum of dice, numbers and symbolic terms
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiceExpression {
+    /// The terms, each with whether it is subtracted
+    terms: Vec<(bool, Term)>,
+}
+
+impl DiceExpression {
+    /// Returns the terms of the expression, each with whether it is subtracted
+    pub fn terms(&self) -> &[(bool, Term)] {
+        &self.terms
+    }
+
+    /// Returns the expression with `value` added to it, or unchanged if `value` is 0
+    pub fn plus(mut self, value: i32) -> Self {
+        if value != 0 {
+            self.terms.push((value < 0, Term::Constant(value.abs())));
+        }
+        self
+    }
+
+    /// Returns a copy of the expression with every symbolic term replaced by its value in `ctx`
+    pub fn resolve<C: DiceContext + ?Sized>(&self, ctx: &C) -> Self {
+        let terms = self
+            .terms
+            .iter()
+            .map(|&(negative, term)| {
+                let value = match term {
+                    Term::Symbol(Symbol::Ability(ability)) => ctx.ability_modifier(ability),
+                    Term::Symbol(Symbol::Proficiency) => ctx.proficiency_bonus(),
+                    Term::Symbol(Symbol::Level) => ctx.level(),
+                    term => return (negative, term),
+                };
+                // Constants are kept positive, as `+-1` can't be parsed
+                (negative != (value < 0), Term::Constant(value.abs()))
+            })
+            .collect();
+
+        Self { terms }
+    }
+
+    /// Sums the values `value` gives each term, passed whether the term is subtracted
+    fn fold<F>(&self, mut value: F) -> Result<i32, DiceError>
+    where
+        F: FnMut(bool, &Term) -> Result<i32, DiceError>,
+    {
+        self.terms.iter().try_fold(0i32, |acc, &(subtract, ref term)| {
+            let value = value(subtract, term)?;
+            if subtract {
+                acc.checked_sub(value)
+            } else {
+                acc.checked_add(value)
+            }
+            .ok_or(DiceError::Overflow)
+        })
+    }
+
+    /// Returns the lowest possible total
+    pub fn min(&self) -> Result<i32, DiceError> {
+        self.fold(|subtract, term| if subtract { term.max() } else { term.min() })
+    }
+
+    /// Returns the highest possible total
+    pub fn max(&self) -> Result<i32, DiceError> {
+        self.fold(|subtract, term| if subtract { term.min() } else { term.max() })
+    }
+
+    /// Returns the average total
+    pub fn average(&self) -> Result<f64, DiceError> {
+        self.terms.iter().try_fold(0.0, |acc, (subtract, term)| {
+            let value = term.average()?;
+            Ok(if *subtract { acc - value } else { acc + value })
+        })
+    }
+
+    /// Rolls the expression with `rng`
+    pub fn roll<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Roll, DiceError> {
+        let mut dice = vec![];
+        let total = self.fold(|_, term| term.roll(rng, &mut dice))?;
+
+        Ok(Roll { total, dice })
+    }
+}
+
+impl fmt::Display for DiceExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (subtract, term)) in self.terms.iter().enumerate() {
+            match (i, subtract) {
+                (_, true) => write!(f, "-")?,
+                (0, false) => {}
+                (_, false) => write!(f, "+")?,
+            }
+            write!(f, "{}", term)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for DiceExpression {
+    type Err = DiceError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input: String = input
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_lowercase();
+
+        let mut terms = vec![];
+        let mut subtract = false;
+        let mut start = 0;
+
+        for (i, c) in input.char_indices().chain(::std::iter::once((input.len(), '+'))) {
+            if c == '+' || c == '-' {
+                if i == 0 && c == '-' {
+                    subtract = true;
+                    start = 1;
+                    continue;
+                }
+                terms.push((subtract, input[start..i].parse()?));
+                subtract = c == '-';
+                start = i + 1;
+            }
+        }
+
+        Ok(Self { terms })
+    }
+}
+
+impl From<Term> for DiceExpression {
+    fn from(term: Term) -> Self {
+        Self {
+            terms: vec![(false, term)],
+        }
+    }
+}
+
+impl Serialize for DiceExpression {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl JsonSchema for DiceExpression {
+    fn schema_name() -> String {
+        String::from("DiceExpression")
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let text = crate::schema::text(
+            "A dice expression",
+            None,
+            &["2d6+3", "1d20+str+prof", "4d6kh3"],
+        );
+
+        crate::schema::text_or::<i32>(gen, text)
+    }
+}
+
+impl<'de> Deserialize<'de> for DiceExpression {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = DiceExpression;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a dice expression such as `2d6+3`")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse().map_err(E::custom)
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                let v = i32::try_from(v)
+                    .ok()
+                    .and_then(i32::checked_abs)
+                    .map(|abs| (v < 0, abs))
+                    .ok_or_else(|| E::custom(format!("`{}` is too large", v)))?;
+
+                Ok(DiceExpression {
+                    terms: vec![(v.0, Term::Constant(v.1))],
+                })
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                i32::try_from(v)
+                    .map(|v| Term::Constant(v).into())
+                    .map_err(|_| E::custom(format!("`{}` is too large", v)))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+    use serde_derive::{Deserialize, Serialize};
+
+    struct Ctx;
+
+    impl DiceContext for Ctx {
+        fn ability_modifier(&self, ability: Ability) -> i32 {
+            match ability {
+                Ability::Strength => 3,
+                _ => -1,
+            }
+        }
+
+        fn proficiency_bonus(&self) -> i32 {
+            2
+        }
+
+        fn level(&self) -> i32 {
+            5
+        }
+    }
+
+    fn expr(s: &str) -> DiceExpression {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_plus() {
+        assert_eq!("1d8+3", expr("1d8").plus(3).to_string());
+        assert_eq!("2d6-1", expr("2d6").plus(-1).to_string());
+        assert_eq!("1d4", expr("1d4").plus(0).to_string());
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!("2d6+3", expr("2d6 + 3").to_string());
+        assert_eq!("1d20+str+prof", expr("d20+STR+prof").to_string());
+        assert_eq!("4d6kh3", expr("4d6k3").to_string());
+        assert_eq!("-1+2d4kl1-dex", expr("-1+2d4kl1-dexterity").to_string());
+        assert_eq!(
+            vec![
+                (
+                    false,
+                    Term::Dice {
+                        count: 1,
+                        sides: 8,
+                        keep: None
+                    }
+                ),
+                (true, Term::Symbol(Symbol::Level)),
+            ],
+            expr("1d8-level").terms()
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!("2d6+".parse::<DiceExpression>().is_err());
+        assert!("2d".parse::<DiceExpression>().is_err());
+        assert!("0d6".parse::<DiceExpression>().is_err());
+        assert!("1d20+luck".parse::<DiceExpression>().is_err());
+        assert!("4d6kx3".parse::<DiceExpression>().is_err());
+        assert!("999999999d6".parse::<DiceExpression>().is_err());
+        assert!("1d4294967295".parse::<DiceExpression>().is_err());
+        assert!("1d20+4294967295".parse::<DiceExpression>().is_err());
+        assert_eq!(Ok(MAX_DICE * MAX_SIDES), expr("1000d1000").max().map(|m| m as u32));
+    }
+
+    #[test]
+    fn test_min_max_average() {
+        let e = expr("2d6+3");
+        assert_eq!(Ok(5), e.min());
+        assert_eq!(Ok(15), e.max());
+        assert_eq!(Ok(10.0), e.average());
+
+        let e = expr("1d4-1d6");
+        assert_eq!(Ok(-5), e.min());
+        assert_eq!(Ok(3), e.max());
+
+        let e = expr("4d6kh3");
+        assert_eq!(Ok(3), e.min());
+        assert_eq!(Ok(18), e.max());
+        assert!((e.average().unwrap() - 15869.0 / 1296.0).abs() < 1e-9);
+
+        let e = expr("2d20kl1");
+        assert!((e.average().unwrap() - 7.175).abs() < 1e-9);
+
+        assert_eq!(Ok(100_100.0), expr("200d1000").average());
+        let e = expr("1000d1000kh999");
+        assert!((e.average().unwrap() - 500_499.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(Err(DiceError::Overflow), expr("2147483647+1").max());
+        assert_eq!(Err(DiceError::Overflow), expr("-2147483647-1d4").min());
+        assert_eq!(
+            Err(DiceError::Overflow),
+            expr("2147483647+1d4").roll(&mut StdRng::seed_from_u64(0))
+        );
+        assert_eq!(Ok(i32::MAX), expr("2147483647").max());
+    }
+
+    #[test]
+    fn test_symbols_must_be_resolved() {
+        let e = expr("1d20+str+prof");
+
+        assert_eq!(
+            Err(DiceError::Unresolved(Symbol::Ability(Ability::Strength))),
+            e.max()
+        );
+        assert_eq!(expr("1d20+3+2"), e.resolve(&Ctx));
+        assert_eq!(Ok(25), e.resolve(&Ctx).max());
+        assert_eq!(Ok(6), expr("level-dex").resolve(&Ctx).min());
+
+        // A negative value is subtracted, so the expression can be parsed again
+        let resolved = expr("1d20+dex-dex").resolve(&Ctx);
+        assert_eq!("1d20-1+1", resolved.to_string());
+        assert_eq!(Ok(resolved.clone()), resolved.to_string().parse());
+    }
+
+    #[test]
+    fn test_seeded_roll() {
+        let e = expr("4d6kh3+1");
+        let first = e.roll(&mut StdRng::seed_from_u64(42)).unwrap();
+        let second = e.roll(&mut StdRng::seed_from_u64(42)).unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(4, first.dice.len());
+
+        let mut dice = first.dice.clone();
+        dice.sort_unstable();
+        assert_eq!(dice[1..].iter().sum::<u32>() as i32 + 1, first.total);
+
+        for seed in 0..100 {
+            let total = e.roll(&mut StdRng::seed_from_u64(seed)).unwrap().total;
+            assert!((4..=19).contains(&total));
+        }
+    }
+
+    #[test]
+    fn test_deserialize() {
+        #[derive(Deserialize, Serialize, Debug, PartialEq)]
+        struct Weapon {
+            damage: DiceExpression,
+            bonus: DiceExpression,
+        }
+
+        let weapon: Weapon = serde_yaml::from_str("damage: 1d8+str\nbonus: 2").unwrap();
+
+        assert_eq!(expr("1d8+str"), weapon.damage);
+        assert_eq!(expr("2"), weapon.bonus);
+        assert_eq!(
+            weapon,
+            serde_yaml::from_str(&serde_yaml::to_string(&weapon).unwrap()).unwrap()
+        );
+        assert!(serde_yaml::from_str::<Weapon>("damage: 1d8+\nbonus: 2").is_err());
+
+        let weapon: Weapon = serde_yaml::from_str("damage: 1d4\nbonus: -1").unwrap();
+        assert_eq!("-1", weapon.bonus.to_string());
+        assert_eq!(
+            weapon,
+            serde_yaml::from_str(&serde_yaml::to_string(&weapon).unwrap()).unwrap()
+        );
+        assert!(serde_yaml::from_str::<Weapon>("damage: 1d4\nbonus: 4294967296").is_err());
+    }
+}
@@ -6,19 +6,401 @@ extern crate serde_yaml;
 pub mod api;
 pub mod character_sheet;
 pub mod assets;
+pub mod currency;
+pub mod dice;
+pub mod library;
+pub mod rules;
+pub mod schema;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+pub mod units;
+pub mod validation;
+use std::fmt;
+use std::path::Path;
+use serde_derive::Serialize;
 use crate::assets::*;
+use crate::assets::character::Ability;
 
 /// Validation Error
-#[derive(Debug, Clone)]
-pub struct ValidatorError;
+///
+/// Serializes as an object with the variant in `error`, such as
+/// `{"error": "unknown-asset", "kind": "feat", "id": "alert"}`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "error", rename_all = "kebab-case")]
+pub enum ValidatorError {
+    /// A file could not be read
+    Read {
+        /// Path of the file
+        path: String,
+        /// What went wrong
+        message: String,
+    },
+    /// An Asset could not be parsed
+    Parse {
+        /// Path of the file the Asset is in, if it came from one
+        path: Option<String>,
+        /// What went wrong
+        message: String,
+    },
+    /// An Asset is referenced that has no definition
+    UnknownAsset {
+        /// Type of the Asset
+        kind: String,
+        /// Unique ID of the Asset
+        id: String,
+    },
+    /// An Asset has the same type and Unique ID as another Asset
+    DuplicateAsset {
+        /// Type of the Asset
+        kind: String,
+        /// Unique ID of the Asset
+        id: String,
+    },
+    /// The Character's level is higher than their experience points allow
+    LevelExceedsExperience {
+        /// The Character's level
+        level: i8,
+        /// The Character's experience points
+        experience: u32,
+    },
+    /// An Ability Score is higher than its maximum
+    AbilityScoreAboveMax {
+        /// The ability
+        ability: Ability,
+        /// The final score
+        score: i8,
+        /// The highest the score may be
+        max: i8,
+    },
+    /// A base Ability Score is outside of the absolute limits
+    BaseAbilityScoreOutOfRange {
+        /// The ability
+        ability: Ability,
+        /// The base score
+        score: i8,
+    },
+    /// A base Ability Score can't be bought with point buy
+    PointBuyScoreOutOfRange {
+        /// The ability
+        ability: Ability,
+        /// The base score
+        score: i8,
+    },
+    /// More points were spent on point buy than there are
+    PointBuyOverBudget {
+        /// Points spent
+        cost: u8,
+        /// Points available
+        budget: u8,
+    },
+    /// The base Ability Scores are not the standard array
+    NotStandardArray {
+        /// The base scores, from highest to lowest
+        scores: Vec<i8>,
+    },
+    /// An Ability has no recorded roll
+    MissingRoll {
+        /// The ability
+        ability: Ability,
+    },
+    /// A recorded roll has a die that a d6 can't roll
+    InvalidRoll {
+        /// The ability
+        ability: Ability,
+        /// The dice rolled
+        dice: [u8; 4],
+    },
+    /// A base Ability Score doesn't match its recorded roll
+    RollMismatch {
+        /// The ability
+        ability: Ability,
+        /// The base score
+        score: i8,
+        /// The score that was rolled
+        rolled: i8,
+    },
+    /// A class has more ASIs than it has granted
+    TooManyAbilityScoreImprovements {
+        /// Unique ID of the class
+        class: String,
+        /// Number of ASIs taken
+        taken: usize,
+        /// Number of ASIs the class has granted at its level
+        allowed: usize,
+    },
+    /// A Feat was taken without meeting its prerequisite
+    FeatPrerequisiteNotMet {
+        /// Unique ID of the Feat
+        id: String,
+    },
+    /// A Feat that increases an ability was taken without choosing one
+    MissingFeatAbility {
+        /// Unique ID of the Feat
+        id: String,
+    },
+    /// The ability chosen for a Feat is not one it can increase
+    FeatAbilityNotAllowed {
+        /// Unique ID of the Feat
+        id: String,
+        /// The ability chosen
+        ability: Ability,
+    },
+    /// A class has reached the level to choose a SubClass but has none
+    MissingSubClass {
+        /// Unique ID of the class
+        class: String,
+    },
+    /// A class has more than one SubClass
+    TooManySubClasses {
+        /// Unique ID of the class
+        class: String,
+        /// How many SubClasses it has
+        count: usize,
+    },
+    /// A SubClass was chosen before its class reached the level to choose one
+    SubClassBeforeLevel {
+        /// Unique ID of the class
+        class: String,
+        /// The level a SubClass is chosen at, if the class has SubClasses
+        level: Option<i8>,
+    },
+    /// A SubClass was chosen for a class it is not a SubClass of
+    SubClassOfOtherClass {
+        /// Unique ID of the SubClass
+        id: String,
+        /// Unique ID of the class it was chosen for
+        class: String,
+    },
+    /// A SubRace was chosen for a race it is not a SubRace of
+    SubRaceOfOtherRace {
+        /// Unique ID of the SubRace
+        id: String,
+        /// Unique ID of the Character's race
+        race: String,
+    },
+    /// Items were put inside of an Item that is not a container
+    NotAContainer {
+        /// Unique ID of the Item
+        id: String,
+    },
+    /// An Item that doesn't need attunement is attuned to
+    NotAttunable {
+        /// Unique ID of the Item
+        id: String,
+    },
+    /// An Item inside of a container is equipped or attuned to
+    StowedItemInUse {
+        /// Unique ID of the Item
+        id: String,
+    },
+    /// More Items are attuned to than a Character can be
+    TooManyAttunedItems {
+        /// How many Items are attuned to
+        count: usize,
+    },
+    /// More than one of a kind of Item that only one can be worn of is equipped
+    TooManyEquipped {
+        /// The kind of Item, such as armor
+        kind: String,
+        /// How many are equipped
+        count: usize,
+    },
+    /// A Feature with options was taken without choosing one
+    MissingFeatureOption {
+        /// Unique ID of the Feature
+        feature: String,
+    },
+    /// The option chosen for a Feature is not one of its options
+    UnknownFeatureOption {
+        /// Unique ID of the Feature
+        feature: String,
+        /// Unique ID of the option
+        option: String,
+    },
+    /// The condition of the option chosen for a Feature is not met
+    FeatureOptionConditionNotMet {
+        /// Unique ID of the Feature
+        feature: String,
+        /// Unique ID of the option
+        option: String,
+    },
+}
+
+impl ValidatorError {
+    fn read(path: &Path, e: ::std::io::Error) -> Self {
+        Self::Read {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        }
+    }
+
+    /// Sets the path of the file that a [`ValidatorError::Parse`] happened in
+    fn at(self, path: &Path) -> Self {
+        match self {
+            Self::Parse { message, .. } => Self::Parse {
+                path: Some(path.display().to_string()),
+                message,
+            },
+            e => e,
+        }
+    }
+
+    /// Returns the error as an object with its message in `message`, such as
+    /// `{"error": "parse", "path": null, "message": "..."}`
+    pub fn diagnostic(&self) -> serde_json::Value {
+        let mut diagnostic = serde_json::to_value(self)
+            .expect("a ValidatorError serializes, as it has no maps with keys that aren't strings");
+        if let serde_json::Value::Object(fields) = &mut diagnostic {
+            fields.insert(String::from("message"), self.to_string().into());
+        }
+        diagnostic
+    }
+}
+
+impl fmt::Display for ValidatorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Read { path, message } => write!(f, "could not read {}: {}", path, message),
+            Self::Parse {
+                path: Some(path),
+                message,
+            } => write!(f, "could not parse {}: {}", path, message),
+            Self::Parse {
+                path: None,
+                message,
+            } => write!(f, "could not parse asset: {}", message),
+            Self::UnknownAsset { kind, id } => write!(f, "no {} with the id `{}`", kind, id),
+            Self::DuplicateAsset { kind, id } => {
+                write!(f, "another {} has the id `{}`", kind, id)
+            }
+            Self::LevelExceedsExperience { level, experience } => write!(
+                f,
+                "level {} needs more than {} experience points (allows level {})",
+                level,
+                experience,
+                rules::level_for_experience(*experience)
+            ),
+            Self::AbilityScoreAboveMax {
+                ability,
+                score,
+                max,
+            } => write!(f, "{} is {}, above its maximum of {}", ability, score, max),
+            Self::BaseAbilityScoreOutOfRange { ability, score } => write!(
+                f,
+                "base {} of {} is outside of {}..={}",
+                ability,
+                score,
+                rules::BASE_ABILITY_SCORE_MIN,
+                rules::BASE_ABILITY_SCORE_MAX
+            ),
+            Self::PointBuyScoreOutOfRange { ability, score } => {
+                write!(f, "base {} of {} can't be bought with point buy", ability, score)
+            }
+            Self::PointBuyOverBudget { cost, budget } => write!(
+                f,
+                "point buy costs {} points but only {} are available",
+                cost, budget
+            ),
+            Self::NotStandardArray { scores } => write!(
+                f,
+                "base scores {:?} are not the standard array {:?}",
+                scores,
+                rules::STANDARD_ARRAY
+            ),
+            Self::MissingRoll { ability } => write!(f, "{} has no recorded roll", ability),
+            Self::InvalidRoll { ability, dice } => {
+                write!(f, "{} roll {:?} has a die that isn't a d6", ability, dice)
+            }
+            Self::RollMismatch {
+                ability,
+                score,
+                rolled,
+            } => write!(
+                f,
+                "base {} of {} doesn't match the {} that was rolled",
+                ability, score, rolled
+            ),
+            Self::TooManyAbilityScoreImprovements {
+                class,
+                taken,
+                allowed,
+            } => write!(
+                f,
+                "{} has {} ability score improvements but only grants {}",
+                class, taken, allowed
+            ),
+            Self::FeatPrerequisiteNotMet { id } => {
+                write!(f, "prerequisite for the feat `{}` is not met", id)
+            }
+            Self::MissingFeatAbility { id } => {
+                write!(f, "no ability was chosen to increase with the feat `{}`", id)
+            }
+            Self::FeatAbilityNotAllowed { id, ability } => {
+                write!(f, "the feat `{}` can't increase {}", id, ability)
+            }
+            Self::MissingSubClass { class } => write!(f, "the class `{}` has no subclass", class),
+            Self::TooManySubClasses { class, count } => {
+                write!(f, "the class `{}` has {} subclasses instead of 1", class, count)
+            }
+            Self::SubClassBeforeLevel {
+                class,
+                level: Some(level),
+            } => write!(
+                f,
+                "the class `{}` can't have a subclass until level {}",
+                class, level
+            ),
+            Self::SubClassBeforeLevel { class, level: None } => {
+                write!(f, "the class `{}` has no subclasses", class)
+            }
+            Self::SubClassOfOtherClass { id, class } => {
+                write!(f, "`{}` is not a subclass of the class `{}`", id, class)
+            }
+            Self::SubRaceOfOtherRace { id, race } => {
+                write!(f, "`{}` is not a subrace of the race `{}`", id, race)
+            }
+            Self::NotAContainer { id } => write!(f, "the item `{}` can't hold other items", id),
+            Self::NotAttunable { id } => write!(f, "the item `{}` can't be attuned to", id),
+            Self::StowedItemInUse { id } => write!(
+                f,
+                "the item `{}` is inside a container, so it can't be equipped or attuned to",
+                id
+            ),
+            Self::TooManyAttunedItems { count } => write!(
+                f,
+                "{} items are attuned to, but only {} can be",
+                count,
+                rules::MAX_ATTUNED_ITEMS
+            ),
+            Self::TooManyEquipped { kind, count } => {
+                write!(f, "{} {} items are equipped, but only 1 can be", count, kind)
+            }
+            Self::MissingFeatureOption { feature } => {
+                write!(f, "no option was chosen for the feature `{}`", feature)
+            }
+            Self::UnknownFeatureOption { feature, option } => {
+                write!(f, "the feature `{}` has no option `{}`", feature, option)
+            }
+            Self::FeatureOptionConditionNotMet { feature, option } => write!(
+                f,
+                "condition for the option `{}` of the feature `{}` is not met",
+                option, feature
+            ),
+        }
+    }
+}
 
 /// Reads in an asset
-pub fn read_asset(path: &::std::path::Path) -> Result<Asset, ValidatorError> {
-    let f = ::std::fs::File::open(path).unwrap();
+pub fn read_asset(path: &Path) -> Result<Asset, ValidatorError> {
+    let f = ::std::fs::File::open(path).map_err(|e| ValidatorError::read(path, e))?;
 
     let reader = ::std::io::BufReader::new(f);
 
-    let deserialized: Asset = ::serde_yaml::from_reader(reader).unwrap();
+    let deserialized: Asset =
+        ::serde_yaml::from_reader(reader).map_err(|e| ValidatorError::Parse {
+            path: Some(path.display().to_string()),
+            message: e.to_string(),
+        })?;
 
     Ok(deserialized)
 }
@@ -26,7 +408,7 @@ pub fn read_asset(path: &::std::path::Path) -> Result<Asset, ValidatorError> {
 #[cfg(test)]
 mod tests {
     use crate::assets::character::{
-        Abilities, Character, CharacterAssetGrant, CharacterClass, CharacterClassSpec,
+        Abilities, Ability, Character, CharacterAssetGrant, CharacterClass, CharacterClassSpec,
         CharacterRace, ASI, CharacterSpec,
     };
 
@@ -53,6 +435,11 @@ mod tests {
                     wisdom: 10,
                     charisma: 13,
                 },
+                generation: Default::default(),
+                experience: 0,
+                options: Default::default(),
+                inventory: Default::default(),
+                currency: Default::default(),
                 assets: character::CharacterAssets {
                     class: CharacterClassSpec::Single(CharacterClass {
                         id: String::from("fighter"),
@@ -61,13 +448,14 @@ mod tests {
                         grants: vec![
                             CharacterAssetGrant::ASI(
                                 ASI::Ability(
-                                    String::from("strength"),
-                                    Some(String::from("dexterity")),
+                                    Ability::Strength,
+                                    Some(Ability::Dexterity),
                                 )
                             ),
                             CharacterAssetGrant::ASI(
                                 ASI::Feat{
                                     id: String::from("this feat"),
+                                    ability: None,
                                     grants: vec![],
                                 },
                             )
@@ -77,6 +465,7 @@ mod tests {
                         id: String::from("elf"),
                         grants: vec![],
                     },
+                    background: None,
                 },
             },
         });
@@ -97,14 +486,19 @@ mod tests {
                 extra: Default::default(),
             },
             spec: Spec::Class {
-                set: Setter::Class { hit_dice: Die::D10 },
+                set: Setter::Class {
+                    hit_dice: Die::D10,
+                    subclass_level: None,
+                },
                 grant: vec![
                     Grant::Proficiency {
                         id: "armor-light".to_string(),
-                    },
+                    }
+                    .into(),
                     Grant::Proficiency {
                         id: "armor-medium".to_string(),
-                    },
+                    }
+                    .into(),
                 ],
                 select: vec![Select::Proficiency(SelectVariant {
                     name: "Skill Proficiency".to_string(),
@@ -125,4 +519,34 @@ mod tests {
 
         assert_eq!(expected, read_asset(file_path).unwrap());
     }
+
+    #[test]
+    fn test_error_serialize() {
+        let error = ValidatorError::UnknownAsset {
+            kind: String::from("feat"),
+            id: String::from("alert"),
+        };
+
+        assert_eq!(
+            serde_json::json!({ "error": "unknown-asset", "kind": "feat", "id": "alert" }),
+            serde_json::to_value(&error).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_error_diagnostic() {
+        let error = ValidatorError::Parse {
+            path: None,
+            message: String::from("oops"),
+        };
+
+        assert_eq!(
+            serde_json::json!({
+                "error": "parse",
+                "path": null,
+                "message": "could not parse asset: oops",
+            }),
+            error.diagnostic()
+        );
+    }
 }
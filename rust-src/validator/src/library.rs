@@ -0,0 +1,338 @@
+//! A collection of Asset definitions that Characters are built from.
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::assets::race::{Race, SubRace, Trait};
+use crate::assets::background::Background;
+use crate::assets::class::SubClass;
+use crate::assets::item::Item;
+use crate::assets::{feat::Feat, feature::Feature, Asset, Die, Setter, Spec};
+use crate::assets::MetaData;
+use crate::ValidatorError;
+
+/// Asset definitions, looked up by their type and id
+#[derive(Debug, Default, PartialEq)]
+pub struct Library {
+    assets: HashMap<&'static str, HashMap<String, Asset>>,
+}
+
+impl Library {
+    /// Returns an empty Library
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an Asset, returning the definition it replaced, if any
+    pub fn insert(&mut self, asset: Asset) -> Option<Asset> {
+        let id = asset.metadata().id.clone();
+
+        self.assets.entry(asset.kind()).or_default().insert(id, asset)
+    }
+
+    /// Returns the Asset with the type `kind` and the id `id`
+    pub fn get(&self, kind: &str, id: &str) -> Option<&Asset> {
+        self.assets.get(kind)?.get(id)
+    }
+
+    /// Returns every Asset in the Library
+    pub fn iter(&self) -> impl Iterator<Item = &Asset> {
+        self.assets.values().flat_map(|assets| assets.values())
+    }
+
+    /// Returns every Asset in the Library, taking them out of it
+    pub fn into_assets(self) -> impl Iterator<Item = Asset> {
+        self.assets.into_values().flat_map(HashMap::into_values)
+    }
+
+    /// Returns the number of Assets in the Library
+    pub fn len(&self) -> usize {
+        self.assets.values().map(|assets| assets.len()).sum()
+    }
+
+    /// Returns whether the Library has no Assets
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the Class with the id `id`
+    pub fn class(&self, id: &str) -> Option<(&MetaData, &Spec)> {
+        match self.get("class", id) {
+            Some(Asset::Class { metadata, spec }) => Some((metadata, spec)),
+            _ => None,
+        }
+    }
+
+    /// Returns the SubClass with the id `id`
+    pub fn sub_class(&self, id: &str) -> Option<&SubClass> {
+        match self.get("sub-class", id) {
+            Some(Asset::SubClass(sub_class)) => Some(sub_class),
+            _ => None,
+        }
+    }
+
+    /// Returns the level at which the Class with the id `id` chooses a SubClass,
+    /// if it has SubClasses
+    pub fn sub_class_level(&self, id: &str) -> Option<i8> {
+        match self.class(id) {
+            Some((_, Spec::Class { set: Setter::Class { subclass_level, .. }, .. })) => {
+                *subclass_level
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the Hit Die of the Class with the id `id`
+    pub fn hit_die(&self, id: &str) -> Option<Die> {
+        match self.class(id) {
+            Some((_, Spec::Class { set: Setter::Class { hit_dice, .. }, .. })) => Some(*hit_dice),
+            _ => None,
+        }
+    }
+
+    /// Returns the Feat with the id `id`
+    pub fn feat(&self, id: &str) -> Option<&Feat> {
+        match self.get("feat", id) {
+            Some(Asset::Feat(feat)) => Some(feat),
+            _ => None,
+        }
+    }
+
+    /// Returns the Feature with the id `id`
+    pub fn feature(&self, id: &str) -> Option<&Feature> {
+        match self.get("feature", id) {
+            Some(Asset::Feature(feature)) => Some(feature),
+            _ => None,
+        }
+    }
+
+    /// Returns the Background with the id `id`
+    pub fn background(&self, id: &str) -> Option<&Background> {
+        match self.get("background", id) {
+            Some(Asset::Background(background)) => Some(background),
+            _ => None,
+        }
+    }
+
+    /// Returns the Item with the id `id`
+    pub fn item(&self, id: &str) -> Option<&Item> {
+        match self.get("item", id) {
+            Some(Asset::Item(item)) => Some(item),
+            _ => None,
+        }
+    }
+
+    /// Returns the Race with the id `id`
+    pub fn race(&self, id: &str) -> Option<&Race> {
+        match self.get("race", id) {
+            Some(Asset::Race(race)) => Some(race),
+            _ => None,
+        }
+    }
+
+    /// Returns the SubRace with the id `id`
+    pub fn sub_race(&self, id: &str) -> Option<&SubRace> {
+        match self.get("sub-race", id) {
+            Some(Asset::SubRace(sub_race)) => Some(sub_race),
+            _ => None,
+        }
+    }
+
+    /// Returns the Trait with the id `id`
+    pub fn race_trait(&self, id: &str) -> Option<&Trait> {
+        match self.get("trait", id) {
+            Some(Asset::Trait(t)) => Some(t),
+            _ => None,
+        }
+    }
+
+    /// Adds every Asset in a (possibly multi-document) yaml string
+    pub fn add_yaml_str(&mut self, yaml: &str) -> Result<(), ValidatorError> {
+        for asset in parse_yaml_str(yaml)? {
+            self.insert(asset);
+        }
+
+        Ok(())
+    }
+
+    /// Adds every Asset in the yaml file, or in every yaml file under the directory, at `path`
+    pub fn add_path(&mut self, path: &Path) -> Result<(), ValidatorError> {
+        for file in files(path)? {
+            for asset in read_assets(&file)? {
+                self.insert(asset);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Loads every Asset at `path`, see [`Library::add_path`]
+    pub fn load(path: &Path) -> Result<Self, ValidatorError> {
+        let mut library = Self::new();
+        library.add_path(path)?;
+
+        Ok(library)
+    }
+}
+
+/// Returns every Asset in a (possibly multi-document) yaml string, in the
+/// order they are written
+fn parse_yaml_str(yaml: &str) -> Result<Vec<Asset>, ValidatorError> {
+    serde_yaml::Deserializer::from_str(yaml)
+        .map(|document| {
+            Asset::deserialize(document).map_err(|e| ValidatorError::Parse {
+                path: None,
+                message: e.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Reads every Asset in the (possibly multi-document) yaml file at `path`, in
+/// the order they are written, keeping Assets with the same type and id
+pub fn read_assets(path: &Path) -> Result<Vec<Asset>, ValidatorError> {
+    let yaml = ::std::fs::read_to_string(path).map_err(|e| ValidatorError::read(path, e))?;
+
+    parse_yaml_str(&yaml).map_err(|e| e.at(path))
+}
+
+/// Returns `path` if it is a file, or every yaml file under it, sorted, if it
+/// is a directory
+pub fn files(path: &Path) -> Result<Vec<PathBuf>, ValidatorError> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut entries = ::std::fs::read_dir(path)
+        .map_err(|e| ValidatorError::read(path, e))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| ValidatorError::read(path, e))?;
+    entries.sort();
+
+    let mut files = vec![];
+    for entry in entries {
+        let is_yaml = entry
+            .extension()
+            .is_some_and(|ext| ext == "yml" || ext == "yaml");
+
+        if entry.is_dir() {
+            files.extend(self::files(&entry)?);
+        } else if is_yaml {
+            files.push(entry);
+        }
+    }
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::condition::Conditional;
+    use crate::assets::Grant;
+    use crate::units::Distance;
+
+    #[test]
+    fn test_library_load_multi_document() {
+        let library = Library::load(Path::new("../../data/proficiency")).unwrap();
+
+        assert_eq!(8, library.len());
+        assert_eq!(
+            "Heavy Armor",
+            library.get("proficiency", "armor-heavy").unwrap().metadata().name
+        );
+        assert!(library.get("language", "armor-heavy").is_none());
+    }
+
+    #[test]
+    fn test_read_assets_in_order() {
+        let ids: Vec<String> = read_assets(Path::new("../../data/proficiency/armor.yml"))
+            .unwrap()
+            .into_iter()
+            .map(|asset| asset.metadata().id.clone())
+            .collect();
+
+        assert_eq!(vec!["armor-light", "armor-medium", "armor-heavy"], ids);
+    }
+
+    #[test]
+    fn test_files() {
+        assert_eq!(
+            vec![
+                PathBuf::from("../../data/proficiency/armor-light.yml"),
+                PathBuf::from("../../data/proficiency/armor.yml"),
+                PathBuf::from("../../data/proficiency/weapon.yml"),
+            ],
+            files(Path::new("../../data/proficiency")).unwrap()
+        );
+        assert_eq!(
+            vec![PathBuf::from("../../data/size.yml")],
+            files(Path::new("../../data/size.yml")).unwrap()
+        );
+        // A missing file is returned as it is, to fail when it is read
+        assert_eq!(
+            vec![PathBuf::from("../../data/missing.yml")],
+            files(Path::new("../../data/missing.yml")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_library_into_assets() {
+        let library = Library::load(Path::new("../../data/proficiency")).unwrap();
+
+        assert_eq!(8, library.into_assets().count());
+    }
+
+    #[test]
+    fn test_library_class() {
+        let library = Library::load(Path::new("../../data/class/fighter.yml")).unwrap();
+
+        assert_eq!("Fighter", library.class("fighter").unwrap().0.name);
+        assert_eq!(Some(Die::D10), library.hit_die("fighter"));
+        assert!(library.class("wizard").is_none());
+        assert!(library.hit_die("wizard").is_none());
+    }
+
+    #[test]
+    fn test_library_senses_and_features() {
+        let mut library = Library::new();
+        library.add_path(Path::new("../../data/size.yml")).unwrap();
+        library.add_path(Path::new("../../data/vision.yml")).unwrap();
+        library.add_path(Path::new("../../data/feature")).unwrap();
+
+        assert_eq!(6 + 3 + 9, library.len());
+        assert_eq!("Medium", library.get("size", "medium").unwrap().metadata().name);
+        assert_eq!("Blindsight", library.get("vision", "blindsight").unwrap().metadata().name);
+
+        let blind_fighting = library
+            .feature("fighting-style")
+            .unwrap()
+            .option("blind-fighting")
+            .unwrap();
+        assert_eq!(
+            vec![Conditional::from(Grant::Vision {
+                id: String::from("blindsight"),
+                range: Some(Distance(10)),
+            })],
+            blind_fighting.spec.grant
+        );
+    }
+
+    #[test]
+    fn test_library_items() {
+        let library = Library::load(Path::new("../../data/item")).unwrap();
+
+        assert_eq!(20, library.len());
+        assert_eq!(Some(55.0), library.item("chain-mail").map(|i| i.spec.weight));
+        assert!(library.item("ring-of-protection").unwrap().spec.attunement);
+    }
+
+    #[test]
+    fn test_library_parse_error() {
+        let err = Library::load(Path::new("badClassMock.yml")).unwrap_err();
+
+        assert!(matches!(err, ValidatorError::Parse { path: Some(_), .. }));
+    }
+}
@@ -0,0 +1,222 @@
+//! Tables and formulas straight out of the rules.
+
+/// The highest level a Character can reach
+pub const MAX_LEVEL: i8 = 20;
+
+/// The highest an Ability Score can be increased to, unless something raises it
+pub const ABILITY_SCORE_MAX: i8 = 20;
+
+/// Unique ID of the class feature that grants an Ability Score Improvement
+pub const ABILITY_SCORE_IMPROVEMENT: &str = "ability-score-improvement";
+
+/// The lowest a base Ability Score can be
+pub const BASE_ABILITY_SCORE_MIN: i8 = 1;
+
+/// The highest a base Ability Score can be
+pub const BASE_ABILITY_SCORE_MAX: i8 = 30;
+
+/// Points available to spend on Ability Scores with point buy
+pub const POINT_BUY_BUDGET: u8 = 27;
+
+/// Cost of each score that can be bought with point buy, starting at 8
+pub const POINT_BUY_COSTS: [u8; 8] = [0, 1, 2, 3, 4, 5, 7, 9];
+
+/// The scores in the standard array
+pub const STANDARD_ARRAY: [i8; 6] = [15, 14, 13, 12, 10, 8];
+
+/// Returns the point buy cost of `score`, or [`None`] if it can't be bought.
+pub fn point_buy_cost(score: i8) -> Option<u8> {
+    POINT_BUY_COSTS
+        .get(usize::try_from(score.checked_sub(8)?).ok()?)
+        .copied()
+}
+
+/// Returns the score of a 4d6 roll, dropping the lowest die.
+pub fn rolled_score(dice: [u8; 4]) -> i8 {
+    let lowest = dice.iter().min().copied().unwrap_or_default();
+
+    (dice.iter().map(|&d| d as i16).sum::<i16>() - lowest as i16) as i8
+}
+
+/// Returns the proficiency bonus of a Character at `level`.
+pub fn proficiency_bonus(level: i8) -> i8 {
+    2 + (level.max(1) - 1) / 4
+}
+
+/// Returns the modifier of an Ability Score.
+pub fn ability_modifier(score: i8) -> i8 {
+    (i16::from(score) - 10).div_euclid(2) as i8
+}
+
+/// Returns the passive score of a check with `modifier`, such as passive Perception.
+pub fn passive_score(modifier: i8) -> i8 {
+    modifier.saturating_add(10)
+}
+
+/// Armor Class of a Character wearing no armor, before their Dexterity modifier
+pub const UNARMORED_ARMOR_CLASS: i8 = 10;
+
+/// The most of a Dexterity modifier that counts towards Armor Class in medium armor
+pub const MEDIUM_ARMOR_MAX_DEXTERITY: i8 = 2;
+
+/// The most magic Items a Character can be attuned to at once
+pub const MAX_ATTUNED_ITEMS: usize = 3;
+
+/// How many feet slower heavy armor makes a Character that isn't strong enough for it
+pub const HEAVY_ARMOR_SPEED_PENALTY: u16 = 10;
+
+/// Returns how many feet slower a Character with `strength` is in armor that
+/// needs `requirement` Strength.
+pub fn armor_speed_penalty(strength: i8, requirement: Option<i8>) -> u16 {
+    match requirement {
+        Some(requirement) if strength < requirement => HEAVY_ARMOR_SPEED_PENALTY,
+        _ => 0,
+    }
+}
+
+/// Returns the hit points gained at a level with a `sides` sided Hit Die and
+/// a `constitution` modifier, taking the maximum at a Character's first level
+/// and the average otherwise. At least 1 hit point is gained at every level.
+pub fn level_hit_points(sides: u32, constitution: i8, first: bool) -> i32 {
+    let die = if first { sides } else { sides / 2 + 1 } as i32;
+
+    (die + i32::from(constitution)).max(1)
+}
+
+/// Pounds a Character can carry for each point of Strength
+pub const CARRYING_CAPACITY_PER_STRENGTH: f32 = 15.0;
+
+/// Pounds a Character can push, drag or lift for each point of Strength
+pub const PUSH_DRAG_LIFT_PER_STRENGTH: f32 = 30.0;
+
+/// Pounds for each point of Strength past which a Character is encumbered,
+/// with the variant encumbrance rules
+pub const ENCUMBERED_PER_STRENGTH: f32 = 5.0;
+
+/// Pounds for each point of Strength past which a Character is heavily
+/// encumbered, with the variant encumbrance rules
+pub const HEAVILY_ENCUMBERED_PER_STRENGTH: f32 = 10.0;
+
+/// How many feet slower an encumbered Character is
+pub const ENCUMBERED_SPEED_PENALTY: u16 = 10;
+
+/// How many feet slower a heavily encumbered Character is
+pub const HEAVILY_ENCUMBERED_SPEED_PENALTY: u16 = 20;
+
+/// Returns what weights a Character of the size `size` carries are multiplied by.
+///
+/// Tiny creatures carry half as much, and every size past Medium doubles it.
+pub fn size_carrying_multiplier(size: &str) -> f32 {
+    match size {
+        "tiny" => 0.5,
+        "large" => 2.0,
+        "huge" => 4.0,
+        "gargantuan" => 8.0,
+        _ => 1.0,
+    }
+}
+
+/// Experience points needed to reach each level, indexed by `level - 1`
+pub const EXPERIENCE_THRESHOLDS: [u32; MAX_LEVEL as usize] = [
+    0, 300, 900, 2_700, 6_500, 14_000, 23_000, 34_000, 48_000, 64_000, 85_000, 100_000, 120_000,
+    140_000, 165_000, 195_000, 225_000, 265_000, 305_000, 355_000,
+];
+
+/// Returns the experience points needed to reach `level`, or [`None`] if there is no such level.
+pub fn experience_for_level(level: i8) -> Option<u32> {
+    if (1..=MAX_LEVEL).contains(&level) {
+        Some(EXPERIENCE_THRESHOLDS[level as usize - 1])
+    } else {
+        None
+    }
+}
+
+/// Returns the highest level that `experience` points are enough for.
+pub fn level_for_experience(experience: u32) -> i8 {
+    EXPERIENCE_THRESHOLDS
+        .iter()
+        .take_while(|&&threshold| threshold <= experience)
+        .count() as i8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proficiency_bonus() {
+        assert_eq!(proficiency_bonus(1), 2);
+        assert_eq!(proficiency_bonus(4), 2);
+        assert_eq!(proficiency_bonus(5), 3);
+        assert_eq!(proficiency_bonus(17), 6);
+        assert_eq!(proficiency_bonus(20), 6);
+    }
+
+    #[test]
+    fn test_ability_modifier() {
+        assert_eq!(ability_modifier(1), -5);
+        assert_eq!(ability_modifier(9), -1);
+        assert_eq!(ability_modifier(10), 0);
+        assert_eq!(ability_modifier(11), 0);
+        assert_eq!(ability_modifier(23), 6);
+    }
+
+    #[test]
+    fn test_point_buy_cost() {
+        assert_eq!(point_buy_cost(7), None);
+        assert_eq!(point_buy_cost(8), Some(0));
+        assert_eq!(point_buy_cost(13), Some(5));
+        assert_eq!(point_buy_cost(15), Some(9));
+        assert_eq!(point_buy_cost(16), None);
+        assert_eq!(point_buy_cost(-121), None);
+        assert_eq!(point_buy_cost(i8::MIN), None);
+    }
+
+    #[test]
+    fn test_rolled_score() {
+        assert_eq!(rolled_score([6, 6, 6, 6]), 18);
+        assert_eq!(rolled_score([1, 4, 3, 5]), 12);
+        assert_eq!(rolled_score([2, 2, 1, 2]), 6);
+    }
+
+    #[test]
+    fn test_armor_speed_penalty() {
+        assert_eq!(armor_speed_penalty(8, None), 0);
+        assert_eq!(armor_speed_penalty(14, Some(15)), 10);
+        assert_eq!(armor_speed_penalty(15, Some(15)), 0);
+    }
+
+    #[test]
+    fn test_level_hit_points() {
+        assert_eq!(level_hit_points(10, 2, true), 12);
+        assert_eq!(level_hit_points(10, 2, false), 8);
+        assert_eq!(level_hit_points(6, -1, false), 3);
+        assert_eq!(level_hit_points(6, -5, true), 1);
+    }
+
+    #[test]
+    fn test_size_carrying_multiplier() {
+        assert_eq!(size_carrying_multiplier("tiny"), 0.5);
+        assert_eq!(size_carrying_multiplier("medium"), 1.0);
+        assert_eq!(size_carrying_multiplier("huge"), 4.0);
+        assert_eq!(size_carrying_multiplier(""), 1.0);
+    }
+
+    #[test]
+    fn test_experience_for_level() {
+        assert_eq!(experience_for_level(1), Some(0));
+        assert_eq!(experience_for_level(5), Some(6_500));
+        assert_eq!(experience_for_level(20), Some(355_000));
+        assert_eq!(experience_for_level(0), None);
+        assert_eq!(experience_for_level(21), None);
+    }
+
+    #[test]
+    fn test_level_for_experience() {
+        assert_eq!(level_for_experience(0), 1);
+        assert_eq!(level_for_experience(299), 1);
+        assert_eq!(level_for_experience(300), 2);
+        assert_eq!(level_for_experience(64_999), 10);
+        assert_eq!(level_for_experience(1_000_000), 20);
+    }
+}
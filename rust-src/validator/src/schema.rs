@@ -0,0 +1,204 @@
+//! JSON Schema of Assets, generated from their types so it never drifts from
+//! what the validator accepts.
+//!
+//! The schema can be given to a YAML language server to check Assets while
+//! they are written, such as with a `# yaml-language-server: $schema=...`
+//! comment at the top of the file. A copy is kept in `schema/asset.schema.json`,
+//! which a test checks is up to date; run it with `UPDATE_SCHEMA=1` to rewrite
+//! the copy after changing an Asset type.
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{
+    InstanceType, Metadata, RootSchema, Schema, SchemaObject, StringValidation, SubschemaValidation,
+};
+use schemars::JsonSchema;
+
+use crate::assets::character::{Character, CharacterAssetGrant};
+use crate::assets::condition::Condition;
+use crate::assets::{Asset, Select};
+
+/// Returns the schema of any Asset
+pub fn asset() -> RootSchema {
+    schemars::schema_for!(Asset)
+}
+
+/// Returns the schema of every type an Asset file or grant can be written as,
+/// by name
+pub fn schemas() -> Vec<(&'static str, RootSchema)> {
+    vec![
+        ("asset", asset()),
+        ("character", schemars::schema_for!(Character)),
+        (
+            "character-asset-grant",
+            schemars::schema_for!(CharacterAssetGrant),
+        ),
+        ("select", schemars::schema_for!(Select)),
+        ("condition", schemars::schema_for!(Condition)),
+    ]
+}
+
+/// Returns `schema` as pretty printed JSON
+pub fn to_string(schema: &RootSchema) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(schema)
+}
+
+/// Returns the schema of a value written as a string, such as `10ft`, that
+/// matches `pattern` if there is one
+pub(crate) fn text(description: &str, pattern: Option<&str>, examples: &[&str]) -> SchemaObject {
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        metadata: Some(Box::new(Metadata {
+            description: Some(description.to_string()),
+            examples: examples.iter().map(|&e| e.into()).collect(),
+            ..Default::default()
+        })),
+        string: pattern.map(|pattern| {
+            Box::new(StringValidation {
+                pattern: Some(pattern.to_string()),
+                ..Default::default()
+            })
+        }),
+        ..Default::default()
+    }
+}
+
+/// Returns the schema of a value written as either `text` or a bare `T`, such
+/// as `10ft` or `10`
+pub(crate) fn text_or<T: JsonSchema>(gen: &mut SchemaGenerator, text: SchemaObject) -> Schema {
+    let metadata = text.metadata.clone();
+
+    SchemaObject {
+        metadata,
+        subschemas: Some(Box::new(SubschemaValidation {
+            any_of: Some(vec![text.into(), gen.subschema_for::<T>()]),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn json(schema: &RootSchema) -> Value {
+        serde_json::to_value(schema).unwrap()
+    }
+
+    #[test]
+    fn test_asset_definitions() {
+        let schema = json(&asset());
+
+        for name in [
+            "BackgroundSpec",
+            "CharacterAssetGrant",
+            "Condition",
+            "Conditional_for_Grant",
+            "FeatSpec",
+            "FeatureSpec",
+            "ItemSpec",
+            "RaceSpec",
+            "Select",
+            "SubClassSpec",
+            "SubRaceSpec",
+            "TraitSpec",
+        ] {
+            assert!(
+                schema["definitions"].get(name).is_some(),
+                "no definition of {}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_asset_types() {
+        let schema = json(&asset());
+
+        let mut types: Vec<&str> = schema["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|variant| variant["properties"]["type"]["enum"][0].as_str())
+            .collect();
+        types.sort_unstable();
+
+        assert_eq!(
+            vec![
+                "background",
+                "character",
+                "class",
+                "feat",
+                "feature",
+                "item",
+                "language",
+                "proficiency",
+                "race",
+                "size",
+                "sub-class",
+                "sub-race",
+                "trait",
+                "vision",
+            ],
+            types
+        );
+    }
+
+    #[test]
+    fn test_custom_types() {
+        let schema = json(&asset());
+        let definitions = &schema["definitions"];
+
+        assert_eq!("string", definitions["Cost"]["type"]);
+        assert_eq!("integer", definitions["Distance"]["anyOf"][1]["type"]);
+        assert_eq!("strength", definitions["Ability"]["oneOf"][0]["enum"][0]);
+        assert!(definitions["DiceExpression"]["anyOf"].is_array());
+    }
+
+    #[test]
+    fn test_conditions() {
+        let schema = json(&asset());
+        let condition = &schema["definitions"]["Condition"]["properties"];
+
+        assert!(condition.get("$not").is_some());
+        assert!(condition.get("level").is_some());
+        assert_eq!(
+            "#/definitions/Condition",
+            schema["definitions"]["Conditional_for_Grant"]["properties"]["if"]["anyOf"][0]["$ref"]
+        );
+    }
+
+    #[test]
+    fn test_schemas() {
+        let names: Vec<&str> = schemas().iter().map(|(name, _)| *name).collect();
+
+        assert_eq!(
+            vec![
+                "asset",
+                "character",
+                "character-asset-grant",
+                "select",
+                "condition"
+            ],
+            names
+        );
+        assert!(to_string(&asset()).unwrap().starts_with("{\n  \"$schema\""));
+    }
+
+    #[test]
+    fn test_schema_file() {
+        let path = ::std::path::Path::new("schema/asset.schema.json");
+        let schema = to_string(&asset()).unwrap() + "\n";
+
+        if ::std::env::var_os("UPDATE_SCHEMA").is_some() {
+            ::std::fs::write(path, &schema).unwrap();
+        }
+
+        assert_eq!(
+            ::std::fs::read_to_string(path).unwrap(),
+            schema,
+            "schema/asset.schema.json is out of date, run the tests with UPDATE_SCHEMA=1"
+        );
+    }
+}
@@ -0,0 +1,36 @@
+//! Fixtures shared by the tests of every crate that turns a sheet into something else.
+//!
+//! Only built for tests, or with the `test-util` feature.
+use std::path::{Path, PathBuf};
+
+use crate::assets::character::Character;
+use crate::character_sheet::CharacterSheet;
+use crate::library::Library;
+
+/// Returns the path of `path` inside of the repository's `data` directory
+fn data(path: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../data")
+        .join(path)
+}
+
+/// Returns a Library with the items, traits and fighter class the fixture uses
+pub fn library() -> Library {
+    let mut library = Library::load(&data("item")).unwrap();
+    library.add_path(&data("trait")).unwrap();
+    library.add_path(&data("class/fighter.yml")).unwrap();
+    library
+}
+
+/// Returns the level 2 fighter in `test_util/fighter.yml`, named `name`
+pub fn character(name: &str) -> Character {
+    let mut character: Character =
+        serde_yaml::from_str(include_str!("test_util/fighter.yml")).unwrap();
+    character.metadata.name = String::from(name);
+    character
+}
+
+/// Returns the sheet of [`character`] named `name`, built with [`library`]
+pub fn sheet(name: &str) -> CharacterSheet {
+    CharacterSheet::build(&character(name), &library())
+}
@@ -0,0 +1,120 @@
+//! Units of measurement used by Assets, such as the range of a sense.
+use std::fmt;
+use std::str::FromStr;
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// A distance, in feet
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct Distance(pub u16);
+
+impl Distance {
+    /// Returns the distance in feet
+    pub fn feet(&self) -> u16 {
+        self.0
+    }
+}
+
+/// Parses distances such as `10ft`, `10 feet`, `10'` or a bare `10`
+impl FromStr for Distance {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let number = ["feet", "foot", "ft.", "ft", "'"]
+            .iter()
+            .find_map(|unit| s.strip_suffix(unit))
+            .unwrap_or(s)
+            .trim();
+
+        number
+            .parse()
+            .map(Distance)
+            .map_err(|_| format!("`{}` is not a distance in feet", s))
+    }
+}
+
+impl fmt::Display for Distance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}ft", self.0)
+    }
+}
+
+impl Serialize for Distance {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl JsonSchema for Distance {
+    fn schema_name() -> String {
+        String::from("Distance")
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let text = crate::schema::text(
+            "A distance in feet",
+            Some(r"^\s*[0-9]+\s*(feet|foot|ft\.|ft|')?\s*$"),
+            &["10ft", "30 feet"],
+        );
+
+        crate::schema::text_or::<u16>(gen, text)
+    }
+}
+
+impl<'de> Deserialize<'de> for Distance {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = Distance;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a distance in feet such as `10ft`")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse().map_err(E::custom)
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                u16::try_from(v)
+                    .map(Distance)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                u16::try_from(v)
+                    .map(Distance)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_from_str() {
+        assert_eq!(Ok(Distance(10)), "10ft".parse());
+        assert_eq!(Ok(Distance(60)), "60 feet".parse());
+        assert_eq!(Ok(Distance(120)), "120'".parse());
+        assert_eq!(Ok(Distance(5)), "5".parse());
+        assert!("ten ft".parse::<Distance>().is_err());
+        assert!("-5ft".parse::<Distance>().is_err());
+    }
+
+    #[test]
+    fn test_distance_serde() {
+        assert_eq!(Distance(30), serde_yaml::from_str("30").unwrap());
+        assert_eq!(Distance(30), serde_yaml::from_str("30ft").unwrap());
+        assert_eq!("---\n30ft\n", serde_yaml::to_string(&Distance(30)).unwrap());
+    }
+}
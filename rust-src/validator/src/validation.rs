@@ -0,0 +1,1436 @@
+//! Module to check that a Character actually follows the rules, and that
+//! every other Asset only refers to Assets that exist.
+use crate::assets::character::{
+    Ability, AbilityGeneration, Character, CharacterAssetGrant, GetAllGrants, Leveling, Skill,
+    ASI,
+};
+use crate::assets::condition::{ConditionContext, Conditional};
+use crate::assets::item::ItemKind;
+use crate::assets::{Asset, Grant, Spec};
+use crate::library::Library;
+use crate::{rules, ValidatorError};
+
+/// To validate a struct against the rules
+pub trait Validate {
+    /// Validates self against the Asset definitions in `library`,
+    /// returning every [`ValidatorError`] found.
+    fn validate(&self, library: &Library) -> Result<(), Vec<ValidatorError>>;
+}
+
+impl Validate for Character {
+    fn validate(&self, library: &Library) -> Result<(), Vec<ValidatorError>> {
+        let errors: Vec<ValidatorError> = validate_experience(self)
+            .into_iter()
+            .chain(validate_ability_generation(self))
+            .chain(validate_ability_maximums(self, library))
+            .chain(validate_ability_score_improvements(self, library))
+            .chain(validate_feats(self, library))
+            .chain(validate_features(self, library))
+            .chain(validate_sub_classes(self, library))
+            .chain(validate_sub_races(self, library))
+            .chain(validate_inventory(self, library))
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl Validate for Asset {
+    fn validate(&self, library: &Library) -> Result<(), Vec<ValidatorError>> {
+        if let Asset::Character(character) = self {
+            return character.validate(library);
+        }
+
+        let errors: Vec<ValidatorError> = references(self)
+            .into_iter()
+            .filter(|(kind, id)| !is_defined(kind, id, library))
+            .map(|(kind, id)| ValidatorError::UnknownAsset {
+                kind: String::from(kind),
+                id: String::from(id),
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Returns the type and Unique ID of every Asset `asset` refers to, by its
+/// grants, equipment, feature or the Asset it belongs to
+fn references(asset: &Asset) -> Vec<(&'static str, &str)> {
+    let (grants, mut references): (Vec<&Conditional<Grant>>, Vec<(&'static str, &str)>) =
+        match asset {
+            Asset::Class {
+                spec: Spec::Class { grant, .. },
+                ..
+            } => (grant.iter().collect(), vec![]),
+            Asset::Background(background) => (
+                background.spec.grant.iter().collect(),
+                background
+                    .spec
+                    .equipment
+                    .iter()
+                    .map(|equipment| ("item", equipment.id.as_str()))
+                    .chain(background.spec.feature.iter().map(|id| ("feature", id.as_str())))
+                    .collect(),
+            ),
+            Asset::Race(race) => (
+                race.spec.grant.iter().collect(),
+                race.spec.size.iter().map(|id| ("size", id.as_str())).collect(),
+            ),
+            Asset::SubRace(sub_race) => (
+                sub_race.spec.spec.grant.iter().collect(),
+                sub_race
+                    .spec
+                    .spec
+                    .size
+                    .iter()
+                    .map(|id| ("size", id.as_str()))
+                    .chain([("race", sub_race.spec.race.as_str())])
+                    .collect(),
+            ),
+            Asset::Trait(t) => (t.spec.grant.iter().collect(), vec![]),
+            Asset::Feat(feat) => (feat.spec.grant.iter().collect(), vec![]),
+            Asset::Feature(feature) => (
+                feature
+                    .spec
+                    .effects
+                    .grant
+                    .iter()
+                    .chain(feature.spec.options.iter().flat_map(|o| &o.spec.grant))
+                    .collect(),
+                vec![],
+            ),
+            Asset::SubClass(sub_class) => (
+                sub_class.spec.grant.iter().collect(),
+                vec![("class", sub_class.spec.class.as_str())],
+            ),
+            _ => (vec![], vec![]),
+        };
+
+    // Spells have no Asset yet, and speeds and rolls are not Assets
+    references.extend(grants.into_iter().filter_map(|grant| match &grant.value {
+        Grant::Proficiency { id } => Some(("proficiency", id.as_str())),
+        Grant::Feature { id } => Some(("feature", id.as_str())),
+        Grant::Language { id } => Some(("language", id.as_str())),
+        Grant::Trait { id } => Some(("trait", id.as_str())),
+        Grant::Size { id } => Some(("size", id.as_str())),
+        Grant::Vision { id, .. } => Some(("vision", id.as_str())),
+        Grant::Spell { .. }
+        | Grant::Speed { .. }
+        | Grant::Advantage { .. }
+        | Grant::Disadvantage { .. } => None,
+    }));
+
+    references
+}
+
+/// Returns whether the Asset of type `kind` with the Unique ID `id` is in
+/// `library`. Proficiencies may also be in an Item, Skill or saving throw.
+fn is_defined(kind: &str, id: &str, library: &Library) -> bool {
+    library.get(kind, id).is_some()
+        || kind == "proficiency"
+            && (library.item(id).is_some()
+                || Skill::ALL.iter().any(|skill| skill.proficiency() == id)
+                || Ability::ALL.iter().any(|ability| ability.proficiency() == id))
+}
+
+/// Checks that the Character's experience points are enough for their level.
+///
+/// Characters using [`Leveling::Milestone`] skip this check.
+fn validate_experience(character: &Character) -> Option<ValidatorError> {
+    if character.spec.options.leveling == Leveling::Milestone {
+        return None;
+    }
+
+    let level = character.level();
+    let experience = character.spec.experience;
+
+    if level > rules::level_for_experience(experience) {
+        Some(ValidatorError::LevelExceedsExperience { level, experience })
+    } else {
+        None
+    }
+}
+
+/// Checks that the base Ability Scores are legal for the method that generated them
+fn validate_ability_generation(character: &Character) -> Vec<ValidatorError> {
+    let abilities = &character.spec.abilities;
+
+    match &character.spec.generation {
+        AbilityGeneration::Manual => Ability::ALL
+            .into_iter()
+            .filter(|&ability| {
+                !(rules::BASE_ABILITY_SCORE_MIN..=rules::BASE_ABILITY_SCORE_MAX)
+                    .contains(&abilities[ability])
+            })
+            .map(|ability| ValidatorError::BaseAbilityScoreOutOfRange {
+                ability,
+                score: abilities[ability],
+            })
+            .collect(),
+        AbilityGeneration::PointBuy => {
+            let costs: Vec<Result<u8, ValidatorError>> = Ability::ALL
+                .into_iter()
+                .map(|ability| {
+                    rules::point_buy_cost(abilities[ability]).ok_or(
+                        ValidatorError::PointBuyScoreOutOfRange {
+                            ability,
+                            score: abilities[ability],
+                        },
+                    )
+                })
+                .collect();
+
+            let errors: Vec<ValidatorError> =
+                costs.iter().filter_map(|c| c.clone().err()).collect();
+            let cost: u8 = costs.into_iter().filter_map(Result::ok).sum();
+
+            if !errors.is_empty() {
+                errors
+            } else if cost > rules::POINT_BUY_BUDGET {
+                vec![ValidatorError::PointBuyOverBudget {
+                    cost,
+                    budget: rules::POINT_BUY_BUDGET,
+                }]
+            } else {
+                vec![]
+            }
+        }
+        AbilityGeneration::StandardArray => {
+            let mut scores: Vec<i8> = Ability::ALL.iter().map(|&a| abilities[a]).collect();
+            scores.sort_unstable_by(|a, b| b.cmp(a));
+
+            if scores == rules::STANDARD_ARRAY {
+                vec![]
+            } else {
+                vec![ValidatorError::NotStandardArray { scores }]
+            }
+        }
+        AbilityGeneration::Rolled { rolls } => Ability::ALL
+            .into_iter()
+            .filter_map(|ability| {
+                let Some(&dice) = rolls.get(&ability) else {
+                    return Some(ValidatorError::MissingRoll { ability });
+                };
+
+                let score = abilities[ability];
+                let rolled = rules::rolled_score(dice);
+
+                if dice.iter().any(|d| !(1..=6).contains(d)) {
+                    Some(ValidatorError::InvalidRoll { ability, dice })
+                } else if score != rolled {
+                    Some(ValidatorError::RollMismatch {
+                        ability,
+                        score,
+                        rolled,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Checks that no Ability Score has been increased above its maximum
+fn validate_ability_maximums(character: &Character, library: &Library) -> Vec<ValidatorError> {
+    let scores = character.ability_scores(library);
+    let grants = character.grants(library);
+
+    Ability::ALL
+        .into_iter()
+        .filter_map(|ability| {
+            let max = grants
+                .iter()
+                .filter_map(|grant| match grant {
+                    CharacterAssetGrant::AbilityMax { id, max } if *id == ability => Some(*max),
+                    _ => None,
+                })
+                .fold(rules::ABILITY_SCORE_MAX, i8::max);
+            let score = scores[ability];
+
+            (score > max).then_some(ValidatorError::AbilityScoreAboveMax {
+                ability,
+                score,
+                max,
+            })
+        })
+        .collect()
+}
+
+/// Checks that each class has no more ASIs than the `ability-score-improvement`
+/// features it has granted by its level
+fn validate_ability_score_improvements(
+    character: &Character,
+    library: &Library,
+) -> Vec<ValidatorError> {
+    let ctx = ConditionContext::new(character, library);
+
+    character
+        .classes()
+        .into_iter()
+        .filter_map(|class| {
+            let taken = class
+                .grants
+                .iter()
+                .filter(|g| matches!(g, CharacterAssetGrant::ASI(_)))
+                .count();
+
+            if taken == 0 {
+                return None;
+            }
+
+            let Some((_, Spec::Class { grant, .. })) = library.class(&class.id) else {
+                return Some(ValidatorError::UnknownAsset {
+                    kind: String::from("class"),
+                    id: class.id.clone(),
+                });
+            };
+
+            let ctx = ctx.for_class(&class.id);
+            let allowed = grant
+                .iter()
+                .filter(|g| {
+                    matches!(&g.value, Grant::Feature { id } if id == rules::ABILITY_SCORE_IMPROVEMENT)
+                })
+                .map(|g| g.occurrences(&ctx))
+                .sum();
+
+            (taken > allowed).then(|| ValidatorError::TooManyAbilityScoreImprovements {
+                class: class.id.clone(),
+                taken,
+                allowed,
+            })
+        })
+        .collect()
+}
+
+/// Checks that every Feat taken exists, has its prerequisite met and, for Feats
+/// that increase an ability, that one of its abilities was chosen
+fn validate_feats(character: &Character, library: &Library) -> Vec<ValidatorError> {
+    character
+        .all_grants()
+        .iter()
+        .flat_map(|grant| {
+            let CharacterAssetGrant::ASI(ASI::Feat { id, ability, .. }) = grant else {
+                return vec![];
+            };
+
+            let Some(feat) = library.feat(id) else {
+                return vec![ValidatorError::UnknownAsset {
+                    kind: String::from("feat"),
+                    id: id.clone(),
+                }];
+            };
+
+            // A Feat can't meet its own prerequisite
+            let prerequisite = feat
+                .spec
+                .prerequisite
+                .as_ref()
+                .filter(|prerequisite| {
+                    let grants = character.grants_without_feat(library, id);
+                    !prerequisite.is_met(&ConditionContext::with_grants(character, &grants))
+                })
+                .map(|_| ValidatorError::FeatPrerequisiteNotMet { id: id.clone() });
+
+            let ability = match (&feat.spec.ability, ability) {
+                (None, None) => None,
+                (Some(_), None) => Some(ValidatorError::MissingFeatAbility { id: id.clone() }),
+                (_, Some(ability)) => (!feat.spec.can_increase(*ability)).then(|| {
+                    ValidatorError::FeatAbilityNotAllowed {
+                        id: id.clone(),
+                        ability: *ability,
+                    }
+                }),
+            };
+
+            prerequisite.into_iter().chain(ability).collect()
+        })
+        .collect()
+}
+
+/// Checks that every Feature granted, by the Character or anything it
+/// references, exists, has an option chosen if it has options, and that the
+/// chosen option's condition is met
+fn validate_features(character: &Character, library: &Library) -> Vec<ValidatorError> {
+    let ctx = ConditionContext::new(character, library);
+    let is_feature = |g: &CharacterAssetGrant| matches!(g, CharacterAssetGrant::Feature { .. });
+
+    // Options of class Features are checked against the level of that class
+    let class_grants: Vec<_> = character
+        .classes()
+        .into_iter()
+        .flat_map(|class| {
+            let ctx = ctx.for_class(&class.id);
+            class
+                .grants
+                .iter()
+                .flat_map(|g| g.all_grants())
+                .filter(is_feature)
+                .map(move |g| (g, ctx.clone()))
+        })
+        .collect();
+
+    // Everything else, from the race, background and library assets, is
+    // checked against the whole Character
+    let mut other_grants: Vec<_> = character
+        .grants(library)
+        .into_iter()
+        .filter(is_feature)
+        .collect();
+    for (grant, _) in &class_grants {
+        if let Some(i) = other_grants.iter().position(|g| g == grant) {
+            other_grants.remove(i);
+        }
+    }
+
+    class_grants
+        .into_iter()
+        .chain(other_grants.into_iter().map(|g| (g, ctx.clone())))
+        .filter_map(|(grant, ctx)| {
+            let CharacterAssetGrant::Feature { id, option } = grant else {
+                return None;
+            };
+
+            let Some(feature) = library.feature(&id) else {
+                return Some(ValidatorError::UnknownAsset {
+                    kind: String::from("feature"),
+                    id,
+                });
+            };
+
+            match option {
+                None if feature.spec.options.is_empty() => None,
+                None => Some(ValidatorError::MissingFeatureOption { feature: id }),
+                Some(option) => match feature.option(&option) {
+                    None => Some(ValidatorError::UnknownFeatureOption {
+                        feature: id,
+                        option,
+                    }),
+                    Some(chosen) => chosen
+                        .condition
+                        .as_ref()
+                        .filter(|condition| !condition.is_met(&ctx))
+                        .map(|_| ValidatorError::FeatureOptionConditionNotMet {
+                            feature: id,
+                            option,
+                        }),
+                },
+            }
+        })
+        .collect()
+}
+
+/// Checks that every SubRace the Character has, that is defined in `library`,
+/// is a SubRace of the Character's race
+fn validate_sub_races(character: &Character, library: &Library) -> Vec<ValidatorError> {
+    let race = &character.spec.assets.race.id;
+
+    character
+        .all_grants()
+        .into_iter()
+        .filter_map(|grant| match grant {
+            CharacterAssetGrant::SubRace { id, .. } => {
+                let sub_race = library.sub_race(&id)?;
+                (sub_race.spec.race != *race).then(|| ValidatorError::SubRaceOfOtherRace {
+                    id,
+                    race: race.clone(),
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Checks that each class has exactly one SubClass of its own once it reaches
+/// the level to choose one, and none before
+fn validate_sub_classes(character: &Character, library: &Library) -> Vec<ValidatorError> {
+    character
+        .classes()
+        .into_iter()
+        .filter(|class| library.class(&class.id).is_some())
+        .flat_map(|class| {
+            let chosen: Vec<&String> = class
+                .grants
+                .iter()
+                .filter_map(|grant| match grant {
+                    CharacterAssetGrant::SubClass { id, .. } => Some(id),
+                    _ => None,
+                })
+                .collect();
+
+            let level = library.sub_class_level(&class.id);
+            let count = match level {
+                Some(level) if class.level >= level => match chosen.len() {
+                    0 => Some(ValidatorError::MissingSubClass {
+                        class: class.id.clone(),
+                    }),
+                    1 => None,
+                    count => Some(ValidatorError::TooManySubClasses {
+                        class: class.id.clone(),
+                        count,
+                    }),
+                },
+                _ if chosen.is_empty() => None,
+                _ => Some(ValidatorError::SubClassBeforeLevel {
+                    class: class.id.clone(),
+                    level,
+                }),
+            };
+
+            let parents = chosen.into_iter().filter_map(|id| match library.sub_class(id) {
+                None => Some(ValidatorError::UnknownAsset {
+                    kind: String::from("sub-class"),
+                    id: id.clone(),
+                }),
+                Some(sub_class) => (sub_class.spec.class != class.id).then(|| {
+                    ValidatorError::SubClassOfOtherClass {
+                        id: id.clone(),
+                        class: class.id.clone(),
+                    }
+                }),
+            });
+
+            count.into_iter().chain(parents).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Checks that every Item carried exists, that only containers hold Items, and
+/// that no more is equipped or attuned to than a Character can manage
+fn validate_inventory(character: &Character, library: &Library) -> Vec<ValidatorError> {
+    let items = character.spec.inventory.all_items();
+
+    let per_item = items.iter().flat_map(|item| {
+        let Some(def) = library.item(&item.id) else {
+            return vec![ValidatorError::UnknownAsset {
+                kind: String::from("item"),
+                id: item.id.clone(),
+            }];
+        };
+
+        let container = (!item.contents.is_empty() && !item.is_container(library))
+            .then(|| ValidatorError::NotAContainer { id: item.id.clone() });
+        let attunable = (item.attuned && !def.spec.attunement)
+            .then(|| ValidatorError::NotAttunable { id: item.id.clone() });
+
+        container.into_iter().chain(attunable).collect()
+    });
+
+    let stowed = character
+        .spec
+        .inventory
+        .stowed_items()
+        .into_iter()
+        .filter(|item| item.equipped || item.attuned)
+        .map(|item| ValidatorError::StowedItemInUse { id: item.id.clone() });
+
+    let attuned = items.iter().filter(|item| item.attuned).count();
+    let attuned = (attuned > rules::MAX_ATTUNED_ITEMS)
+        .then_some(ValidatorError::TooManyAttunedItems { count: attuned });
+
+    let equipped = character.spec.inventory.equipped(library);
+    let worn = ["armor", "shield"].into_iter().filter_map(|kind| {
+        let count = equipped
+            .iter()
+            .filter(|(_, def)| match def.spec.kind {
+                ItemKind::Armor(_) => kind == "armor",
+                ItemKind::Shield { .. } => kind == "shield",
+                _ => false,
+            })
+            .count();
+
+        (count > 1).then(|| ValidatorError::TooManyEquipped {
+            kind: String::from(kind),
+            count,
+        })
+    });
+
+    per_item.chain(stowed).chain(attuned).chain(worn).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIBRARY: &str = "
+type: class
+metadata:
+  id: fighter
+  name: Fighter
+spec:
+  set:
+    hit-dice: d10
+  grant:
+    - type: feature
+      id: ability-score-improvement
+      if:
+        level: [4, 6, 8]
+  select: []
+---
+type: feat
+metadata:
+  id: grappler
+  name: Grappler
+spec:
+  prerequisite:
+    ability:
+      strength: 13
+---
+type: feat
+metadata:
+  id: alert
+  name: Alert
+---
+type: feature
+metadata:
+  id: second-wind
+  name: Second Wind
+---
+type: feature
+metadata:
+  id: ability-score-improvement
+  name: Ability Score Improvement
+---
+type: feature
+metadata:
+  id: fighting-style
+  name: Fighting Style
+spec:
+  options:
+    - metadata:
+        id: archery
+        name: Archery
+      if:
+        class: [fighter, ranger]
+      spec:
+        stat:
+          ranged.attack: 2
+    - metadata:
+        id: defense
+        name: Defense
+      if:
+        class: paladin
+---
+type: feat
+metadata:
+  id: athlete
+  name: Athlete
+spec:
+  prerequisite:
+    ability:
+      strength: 10
+  ability: strength
+---
+type: feat
+metadata:
+  id: armor-master
+  name: Armor Master
+spec:
+  prerequisite:
+    proficiency: armor-heavy
+  grant:
+    - type: proficiency
+      id: armor-heavy
+---
+type: feat
+metadata:
+  id: heavily-armored
+  name: Heavily Armored
+spec:
+  prerequisite:
+    proficiency: armor-medium
+  ability: strength
+  grant:
+    - type: proficiency
+      id: armor-heavy
+";
+
+    fn library() -> Library {
+        let mut library = Library::new();
+        library.add_yaml_str(LIBRARY).unwrap();
+        library
+    }
+
+    fn character(level: i8, experience: u32, leveling: &str, grants: &str) -> Character {
+        serde_yaml::from_str(&format!(
+            "
+metadata:
+  id: uuid-lmao-lol
+  name: foobar
+spec:
+  abilities:
+    strength: 9
+    dexterity: 18
+    constitution: 11
+    intelligence: 11
+    wisdom: 10
+    charisma: 13
+  experience: {}
+  options:
+    leveling: {}
+  class:
+    id: fighter
+    level: {}
+    grants: {}
+  race:
+    id: elf
+    grants:
+      - type: abilityscore
+        id: dexterity
+        add: 2
+",
+            experience, leveling, level, grants
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_experience_enough_for_level() {
+        assert_eq!(character(3, 900, "experience", "[]").validate(&library()), Ok(()));
+        assert_eq!(character(1, 0, "experience", "[]").validate(&library()), Ok(()));
+    }
+
+    #[test]
+    fn test_experience_too_low_for_level() {
+        assert_eq!(
+            character(4, 900, "experience", "[]").validate(&library()),
+            Err(vec![ValidatorError::LevelExceedsExperience {
+                level: 4,
+                experience: 900,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_milestone_leveling_ignores_experience() {
+        assert_eq!(character(4, 0, "milestone", "[]").validate(&library()), Ok(()));
+    }
+
+    #[test]
+    fn test_ability_score_above_max() {
+        let grants = "[{type: asi, ability: [dexterity, strength]}]";
+
+        assert_eq!(
+            character(4, 0, "milestone", grants).validate(&library()),
+            Err(vec![ValidatorError::AbilityScoreAboveMax {
+                ability: Ability::Dexterity,
+                score: 21,
+                max: 20,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_ability_max_grant_raises_max() {
+        let grants = "
+      - type: asi
+        ability: [dexterity, strength]
+      - type: ability-max
+        id: dexterity
+        max: 22";
+
+        assert_eq!(character(4, 0, "milestone", grants).validate(&library()), Ok(()));
+    }
+
+    #[test]
+    fn test_ability_score_improvements_by_level() {
+        let grants = "
+      - type: asi
+        ability: [strength, constitution]
+      - type: asi
+        ability: [strength, constitution]";
+
+        assert_eq!(character(6, 0, "milestone", grants).validate(&library()), Ok(()));
+        assert_eq!(
+            character(5, 0, "milestone", grants).validate(&library()),
+            Err(vec![ValidatorError::TooManyAbilityScoreImprovements {
+                class: String::from("fighter"),
+                taken: 2,
+                allowed: 1,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_ability_score_improvement_unknown_class() {
+        let grants = "[{type: asi, ability: [strength, wisdom]}]";
+
+        assert_eq!(
+            character(4, 0, "milestone", grants).validate(&Library::new()),
+            Err(vec![ValidatorError::UnknownAsset {
+                kind: String::from("class"),
+                id: String::from("fighter"),
+            }])
+        );
+    }
+
+    #[test]
+    fn test_feat_prerequisites() {
+        let alert = "[{type: asi, feat: {id: alert, grants: []}}]";
+        let grappler = "[{type: asi, feat: {id: grappler, grants: []}}]";
+        let tough = "[{type: asi, feat: {id: tough, grants: []}}]";
+
+        assert_eq!(character(4, 0, "milestone", alert).validate(&library()), Ok(()));
+        assert_eq!(
+            character(4, 0, "milestone", grappler).validate(&library()),
+            Err(vec![ValidatorError::FeatPrerequisiteNotMet {
+                id: String::from("grappler"),
+            }])
+        );
+        assert_eq!(
+            character(4, 0, "milestone", tough).validate(&library()),
+            Err(vec![ValidatorError::UnknownAsset {
+                kind: String::from("feat"),
+                id: String::from("tough"),
+            }])
+        );
+    }
+
+    #[test]
+    fn test_feat_prerequisites_without_the_feat() {
+        let athlete = "[{type: asi, feat: {id: athlete, ability: strength}}]";
+        let armor_master = "[{type: asi, feat: {id: armor-master}}]";
+
+        // Strength 9 only reaches 10 with the Feat's own increase
+        assert_eq!(
+            character(4, 0, "milestone", athlete).validate(&library()),
+            Err(vec![ValidatorError::FeatPrerequisiteNotMet {
+                id: String::from("athlete"),
+            }])
+        );
+        assert_eq!(
+            character(
+                4,
+                0,
+                "milestone",
+                "[{type: abilityscore, id: strength, add: 1}, {type: asi, feat: {id: athlete, ability: strength}}]"
+            )
+            .validate(&library()),
+            Ok(())
+        );
+
+        // The Feat grants the Proficiency it needs
+        assert_eq!(
+            character(4, 0, "milestone", armor_master).validate(&library()),
+            Err(vec![ValidatorError::FeatPrerequisiteNotMet {
+                id: String::from("armor-master"),
+            }])
+        );
+        assert_eq!(
+            character(
+                4,
+                0,
+                "milestone",
+                "[{type: proficiency, id: armor-heavy}, {type: asi, feat: {id: armor-master}}]"
+            )
+            .validate(&library()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_feature_options() {
+        let archery = "[{type: feature, id: fighting-style, option: archery}]";
+        let defense = "[{type: feature, id: fighting-style, option: defense}]";
+        let unchosen = "[{type: feature, id: fighting-style}]";
+        let dueling = "[{type: feature, id: fighting-style, option: dueling}]";
+        let second_wind = "[{type: feature, id: second-wind}]";
+
+        assert_eq!(character(1, 0, "milestone", archery).validate(&library()), Ok(()));
+        assert_eq!(character(1, 0, "milestone", second_wind).validate(&library()), Ok(()));
+        assert_eq!(
+            character(1, 0, "milestone", defense).validate(&library()),
+            Err(vec![ValidatorError::FeatureOptionConditionNotMet {
+                feature: String::from("fighting-style"),
+                option: String::from("defense"),
+            }])
+        );
+        assert_eq!(
+            character(1, 0, "milestone", unchosen).validate(&library()),
+            Err(vec![ValidatorError::MissingFeatureOption {
+                feature: String::from("fighting-style"),
+            }])
+        );
+        assert_eq!(
+            character(1, 0, "milestone", dueling).validate(&library()),
+            Err(vec![ValidatorError::UnknownFeatureOption {
+                feature: String::from("fighting-style"),
+                option: String::from("dueling"),
+            }])
+        );
+        assert_eq!(
+            character(1, 0, "milestone", "[{type: feature, id: action-surge}]")
+                .validate(&library()),
+            Err(vec![ValidatorError::UnknownAsset {
+                kind: String::from("feature"),
+                id: String::from("action-surge"),
+            }])
+        );
+    }
+
+    #[test]
+    fn test_feature_options_from_library_assets() {
+        let mut library = library();
+        library
+            .add_yaml_str(
+                "
+type: background
+metadata:
+  id: soldier
+  name: Soldier
+spec:
+  feature: military-rank
+---
+type: trait
+metadata:
+  id: martial-training
+  name: Martial Training
+spec:
+  grant:
+    - type: feature
+      id: fighting-style
+",
+            )
+            .unwrap();
+
+        let trained = "[{type: trait, id: martial-training, grants: []}]";
+        let mut ch = character(1, 0, "milestone", trained);
+        ch.spec.assets.background = serde_yaml::from_str("{id: soldier, grants: []}").unwrap();
+
+        assert_eq!(
+            ch.validate(&library),
+            Err(vec![
+                ValidatorError::UnknownAsset {
+                    kind: String::from("feature"),
+                    id: String::from("military-rank"),
+                },
+                ValidatorError::MissingFeatureOption {
+                    feature: String::from("fighting-style"),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_feat_abilities() {
+        let feat = |rest: &str| {
+            format!(
+                "[{{type: proficiency, id: armor-medium}}, {{type: asi, feat: {{id: heavily-armored{}}}}}]",
+                rest
+            )
+        };
+
+        assert_eq!(
+            character(4, 0, "milestone", &feat(", ability: strength")).validate(&library()),
+            Ok(())
+        );
+        assert_eq!(
+            character(4, 0, "milestone", &feat("")).validate(&library()),
+            Err(vec![ValidatorError::MissingFeatAbility {
+                id: String::from("heavily-armored"),
+            }])
+        );
+        assert_eq!(
+            character(4, 0, "milestone", &feat(", ability: wisdom")).validate(&library()),
+            Err(vec![ValidatorError::FeatAbilityNotAllowed {
+                id: String::from("heavily-armored"),
+                ability: Ability::Wisdom,
+            }])
+        );
+        assert_eq!(
+            character(4, 0, "milestone", "[{type: asi, feat: {id: heavily-armored, ability: strength}}]")
+                .validate(&library()),
+            Err(vec![ValidatorError::FeatPrerequisiteNotMet {
+                id: String::from("heavily-armored"),
+            }])
+        );
+    }
+
+    #[test]
+    fn test_class_grants_from_library() {
+        let improvement = CharacterAssetGrant::Feature {
+            id: String::from("ability-score-improvement"),
+            option: None,
+        };
+
+        assert!(character(4, 0, "milestone", "[]")
+            .grants(&library())
+            .contains(&improvement));
+        assert!(!character(3, 0, "milestone", "[]")
+            .grants(&library())
+            .contains(&improvement));
+    }
+
+    #[test]
+    fn test_sub_race_of_other_race() {
+        let mut library = library();
+        library
+            .add_yaml_str(
+                "
+type: sub-race
+metadata:
+  id: hill-dwarf
+  name: Hill Dwarf
+spec:
+  race: dwarf
+  ability:
+    wisdom: 1
+",
+            )
+            .unwrap();
+
+        let ch = character(1, 0, "milestone", "[{type: sub-race, id: hill-dwarf, grants: []}]");
+
+        assert_eq!(
+            ch.validate(&library),
+            Err(vec![ValidatorError::SubRaceOfOtherRace {
+                id: String::from("hill-dwarf"),
+                race: String::from("elf"),
+            }])
+        );
+        // The SubRace of another race gives nothing
+        assert_eq!(10, ch.ability_scores(&library).wisdom);
+    }
+
+    /// Gives the fighter SubClasses from level 3
+    const SUB_CLASSES: &str = "
+type: class
+metadata:
+  id: fighter
+  name: Fighter
+spec:
+  set:
+    hit-dice: d10
+    subclass-level: 3
+  grant: []
+  select: []
+---
+type: sub-class
+metadata:
+  id: champion
+  name: Champion
+spec:
+  class: fighter
+---
+type: sub-class
+metadata:
+  id: battle-master
+  name: Battle Master
+spec:
+  class: fighter
+---
+type: sub-class
+metadata:
+  id: evocation
+  name: School of Evocation
+spec:
+  class: wizard
+";
+
+    #[test]
+    fn test_sub_class_level() {
+        let mut library = library();
+        library.add_yaml_str(SUB_CLASSES).unwrap();
+
+        let champion = "[{type: sub-class, id: champion, grants: []}]";
+        let both = "[{type: sub-class, id: champion, grants: []}, {type: sub-class, id: battle-master, grants: []}]";
+        let fighter = || String::from("fighter");
+
+        assert_eq!(character(2, 0, "milestone", "[]").validate(&library), Ok(()));
+        assert_eq!(character(3, 0, "milestone", champion).validate(&library), Ok(()));
+        assert_eq!(
+            character(2, 0, "milestone", champion).validate(&library),
+            Err(vec![ValidatorError::SubClassBeforeLevel {
+                class: fighter(),
+                level: Some(3),
+            }])
+        );
+        assert_eq!(
+            character(3, 0, "milestone", "[]").validate(&library),
+            Err(vec![ValidatorError::MissingSubClass { class: fighter() }])
+        );
+        assert_eq!(
+            character(3, 0, "milestone", both).validate(&library),
+            Err(vec![ValidatorError::TooManySubClasses {
+                class: fighter(),
+                count: 2,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_sub_class_of_other_class() {
+        let mut library = library();
+        library.add_yaml_str(SUB_CLASSES).unwrap();
+
+        assert_eq!(
+            character(3, 0, "milestone", "[{type: sub-class, id: evocation, grants: []}]")
+                .validate(&library),
+            Err(vec![ValidatorError::SubClassOfOtherClass {
+                id: String::from("evocation"),
+                class: String::from("fighter"),
+            }])
+        );
+        assert_eq!(
+            character(3, 0, "milestone", "[{type: sub-class, id: eldritch-knight, grants: []}]")
+                .validate(&library),
+            Err(vec![ValidatorError::UnknownAsset {
+                kind: String::from("sub-class"),
+                id: String::from("eldritch-knight"),
+            }])
+        );
+    }
+
+    const ITEMS: &str = "
+type: item
+metadata:
+  id: chain-mail
+  name: Chain Mail
+spec:
+  kind: armor
+  category: heavy
+  ac: 16
+  strength: 13
+---
+type: item
+metadata:
+  id: leather
+  name: Leather
+spec:
+  kind: armor
+  category: light
+  ac: 11
+---
+type: item
+metadata:
+  id: backpack
+  name: Backpack
+spec:
+  kind: container
+  capacity: 30
+---
+type: item
+metadata:
+  id: ring-of-protection
+  name: Ring of Protection
+spec:
+  kind: gear
+  attunement: true
+---
+type: item
+metadata:
+  id: rations
+  name: Rations
+spec:
+  kind: gear
+";
+
+    fn carrying(items: &str) -> Character {
+        let mut character = character(1, 0, "experience", "[]");
+        character.spec.inventory = serde_yaml::from_str(items).unwrap();
+        character
+    }
+
+    #[test]
+    fn test_inventory() {
+        let mut library = library();
+        library.add_yaml_str(ITEMS).unwrap();
+
+        assert_eq!(
+            carrying(
+                "
+items:
+  - {id: chain-mail, equipped: true}
+  - {id: leather}
+  - {id: ring-of-protection, attuned: true}
+  - id: backpack
+    contents:
+      - {id: rations, quantity: 10}"
+            )
+            .validate(&library),
+            Ok(())
+        );
+        assert_eq!(
+            carrying(
+                "
+items:
+  - {id: chain-mail, equipped: true}
+  - {id: leather, equipped: true, attuned: true}
+  - id: rations
+    contents:
+      - {id: potion-of-healing}"
+            )
+            .validate(&library),
+            Err(vec![
+                ValidatorError::NotAttunable {
+                    id: String::from("leather"),
+                },
+                ValidatorError::NotAContainer {
+                    id: String::from("rations"),
+                },
+                ValidatorError::UnknownAsset {
+                    kind: String::from("item"),
+                    id: String::from("potion-of-healing"),
+                },
+                ValidatorError::TooManyEquipped {
+                    kind: String::from("armor"),
+                    count: 2,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_stowed_items_in_use() {
+        let mut library = library();
+        library.add_yaml_str(ITEMS).unwrap();
+
+        assert_eq!(
+            carrying(
+                "
+items:
+  - id: backpack
+    contents:
+      - {id: chain-mail, equipped: true}
+      - {id: leather}
+      - {id: ring-of-protection, attuned: true}"
+            )
+            .validate(&library),
+            Err(vec![
+                ValidatorError::StowedItemInUse {
+                    id: String::from("chain-mail"),
+                },
+                ValidatorError::StowedItemInUse {
+                    id: String::from("ring-of-protection"),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_too_many_attuned_items() {
+        let mut library = library();
+        library.add_yaml_str(ITEMS).unwrap();
+
+        let ring = "{id: ring-of-protection, attuned: true}";
+        let items = format!("items: [{}, {}, {}, {}]", ring, ring, ring, ring);
+
+        assert_eq!(
+            carrying(&items).validate(&library),
+            Err(vec![ValidatorError::TooManyAttunedItems { count: 4 }])
+        );
+    }
+
+    fn generated(scores: [i8; 6], generation: &str) -> Character {
+        serde_yaml::from_str(&format!(
+            "
+metadata:
+  id: uuid-lmao-lol
+  name: foobar
+spec:
+  abilities:
+    strength: {}
+    dexterity: {}
+    constitution: {}
+    intelligence: {}
+    wisdom: {}
+    charisma: {}
+  generation: {}
+  class:
+    id: fighter
+    level: 1
+    grants: []
+  race:
+    id: elf
+    grants: []
+",
+            scores[0], scores[1], scores[2], scores[3], scores[4], scores[5], generation
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_manual_generation() {
+        let lib = library();
+
+        assert_eq!(generated([3, 18, 20, 1, 10, 10], "{method: manual}").validate(&lib), Ok(()));
+        assert_eq!(
+            generated([0, 18, 31, 1, 10, 10], "{method: manual}").validate(&lib),
+            Err(vec![
+                ValidatorError::BaseAbilityScoreOutOfRange {
+                    ability: Ability::Strength,
+                    score: 0,
+                },
+                ValidatorError::BaseAbilityScoreOutOfRange {
+                    ability: Ability::Constitution,
+                    score: 31,
+                },
+                ValidatorError::AbilityScoreAboveMax {
+                    ability: Ability::Constitution,
+                    score: 31,
+                    max: 20,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_point_buy_generation() {
+        let lib = library();
+        let method = "{method: point-buy}";
+
+        assert_eq!(generated([15, 15, 15, 8, 8, 8], method).validate(&lib), Ok(()));
+        assert_eq!(generated([8, 8, 8, 8, 8, 8], method).validate(&lib), Ok(()));
+        assert_eq!(
+            generated([15, 15, 15, 9, 8, 8], method).validate(&lib),
+            Err(vec![ValidatorError::PointBuyOverBudget {
+                cost: 28,
+                budget: 27,
+            }])
+        );
+        assert_eq!(
+            generated([16, 8, 8, 8, 8, 7], method).validate(&lib),
+            Err(vec![
+                ValidatorError::PointBuyScoreOutOfRange {
+                    ability: Ability::Strength,
+                    score: 16,
+                },
+                ValidatorError::PointBuyScoreOutOfRange {
+                    ability: Ability::Charisma,
+                    score: 7,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_standard_array_generation() {
+        let lib = library();
+        let method = "{method: standard-array}";
+
+        assert_eq!(generated([8, 15, 13, 12, 10, 14], method).validate(&lib), Ok(()));
+        assert_eq!(
+            generated([8, 15, 13, 12, 10, 15], method).validate(&lib),
+            Err(vec![ValidatorError::NotStandardArray {
+                scores: vec![15, 15, 13, 12, 10, 8],
+            }])
+        );
+    }
+
+    #[test]
+    fn test_rolled_generation() {
+        let lib = library();
+        let rolls = |charisma: &str| {
+            format!(
+                "
+    method: rolled
+    rolls:
+      strength: [1, 2, 3, 4]
+      dexterity: [6, 6, 6, 1]
+      constitution: [3, 3, 3, 3]
+      intelligence: [2, 5, 4, 4]
+      wisdom: [1, 1, 1, 1]
+      {}",
+                charisma
+            )
+        };
+
+        assert_eq!(
+            generated([9, 18, 9, 13, 3, 10], &rolls("charisma: [4, 4, 2, 1]")).validate(&lib),
+            Ok(())
+        );
+        assert_eq!(
+            generated([9, 18, 9, 13, 4, 10], &rolls("charisma: [4, 4, 2, 7]")).validate(&lib),
+            Err(vec![
+                ValidatorError::RollMismatch {
+                    ability: Ability::Wisdom,
+                    score: 4,
+                    rolled: 3,
+                },
+                ValidatorError::InvalidRoll {
+                    ability: Ability::Charisma,
+                    dice: [4, 4, 2, 7],
+                },
+            ])
+        );
+        assert_eq!(
+            generated([9, 18, 9, 13, 3, 10], &rolls("")).validate(&lib),
+            Err(vec![ValidatorError::MissingRoll {
+                ability: Ability::Charisma,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_asset_references() {
+        let library = library();
+        let asset = |yaml: &str| serde_yaml::from_str::<Asset>(yaml).unwrap();
+        let unknown = |kind: &str, id: &str| ValidatorError::UnknownAsset {
+            kind: String::from(kind),
+            id: String::from(id),
+        };
+
+        let background = asset(
+            "
+type: background
+metadata:
+  id: acolyte
+  name: Acolyte
+spec:
+  skills:
+    - religion
+  equipment:
+    - id: holy-symbol
+  feature: shelter-of-the-faithful
+  grant:
+    - type: proficiency
+      id: saving-throw-wisdom
+    - type: language
+      id: celestial
+    - type: spell
+      id: sanctuary
+",
+        );
+        assert_eq!(
+            background.validate(&library),
+            Err(vec![
+                unknown("item", "holy-symbol"),
+                unknown("feature", "shelter-of-the-faithful"),
+                unknown("language", "celestial"),
+            ])
+        );
+
+        let sub_race = asset(
+            "
+type: sub-race
+metadata:
+  id: high-elf
+  name: High Elf
+spec:
+  race: elf
+  grant:
+    - type: feature
+      id: second-wind
+    - type: proficiency
+      id: skill-perception
+",
+        );
+        assert_eq!(sub_race.validate(&library), Err(vec![unknown("race", "elf")]));
+
+        // The fighter only grants a Feature in the Library
+        assert_eq!(
+            library.get("class", "fighter").unwrap().validate(&library),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! WebAssembly bindings for the validator, so the frontend can parse,
+//! validate and build Characters with the same rules as everything else.
+//!
+//! Characters and Assets are passed in as JSON, or YAML as it is a superset
+//! of JSON, and results are returned as JSON. Errors are thrown as objects,
+//! such as `{"error": "parse", "path": null, "message": "..."}`, with the
+//! fields of the [`ValidatorError`] they are from and its `message`.
+//!
+//! Build with `wasm-pack build rust-src/wasm`, and test in a headless runtime
+//! with `wasm-pack test --node rust-src/wasm`.
+#![warn(missing_docs)]
+use serde::Serialize;
+use serde_json::Value;
+use validator::assets::character::Character;
+use validator::assets::Asset;
+use validator::character_sheet::CharacterSheet;
+use validator::library::Library;
+use validator::validation::Validate;
+use validator::ValidatorError;
+use wasm_bindgen::prelude::*;
+
+/// Assets loaded from a bundle, to validate and build Characters against
+#[wasm_bindgen]
+#[derive(Debug, Default)]
+pub struct AssetBundle {
+    library: Library,
+}
+
+#[wasm_bindgen]
+impl AssetBundle {
+    /// Returns how many Assets are in the bundle
+    #[wasm_bindgen(getter)]
+    pub fn len(&self) -> usize {
+        self.library.len()
+    }
+
+    /// Returns whether the bundle has no Assets
+    #[wasm_bindgen(js_name = isEmpty)]
+    pub fn is_empty(&self) -> bool {
+        self.library.is_empty()
+    }
+}
+
+/// Returns `e` as a plain JavaScript object to throw, or the error converting
+/// it if it can't be
+fn to_js(e: ValidatorError) -> JsValue {
+    to_json(&e.diagnostic())
+        .and_then(|json| js_sys::JSON::parse(&json))
+        .unwrap_or_else(|e| e)
+}
+
+/// Returns `value` as JSON, or a JavaScript error to throw if it can't be
+/// serialized
+fn to_json<T: Serialize>(value: &T) -> Result<String, JsValue> {
+    serde_json::to_string(value)
+        .map_err(|e| js_sys::Error::new(&format!("could not serialize: {}", e)).into())
+}
+
+fn parse_error(e: serde_yaml::Error) -> ValidatorError {
+    ValidatorError::Parse {
+        path: None,
+        message: e.to_string(),
+    }
+}
+
+fn parse(source: &str) -> Result<Character, ValidatorError> {
+    match serde_yaml::from_str(source).map_err(parse_error)? {
+        Asset::Character(character) => Ok(character),
+        _ => Err(ValidatorError::Parse {
+            path: None,
+            message: String::from("the asset is not a character"),
+        }),
+    }
+}
+
+fn load(bundle: &str) -> Result<Library, ValidatorError> {
+    let mut library = Library::new();
+
+    // A JSON bundle is a list of Assets, a YAML one can also be many documents
+    if bundle.trim_start().starts_with('[') {
+        let assets: Vec<Asset> = serde_yaml::from_str(bundle).map_err(parse_error)?;
+        for asset in assets {
+            library.insert(asset);
+        }
+    } else {
+        library.add_yaml_str(bundle)?;
+    }
+
+    Ok(library)
+}
+
+fn check(library: &Library, source: &str) -> Result<Vec<Value>, ValidatorError> {
+    let errors = match parse(source)?.validate(library) {
+        Ok(()) => vec![],
+        Err(errors) => errors.iter().map(ValidatorError::diagnostic).collect(),
+    };
+
+    Ok(errors)
+}
+
+/// Parses a Character, returning it as JSON with every default filled in
+#[wasm_bindgen(js_name = parseCharacter)]
+pub fn parse_character(source: &str) -> Result<String, JsValue> {
+    let character = parse(source).map_err(to_js)?;
+
+    to_json(&Asset::Character(character))
+}
+
+/// Loads a bundle of Assets, either a JSON list of Assets or YAML documents
+#[wasm_bindgen(js_name = loadAssetBundle)]
+pub fn load_asset_bundle(bundle: &str) -> Result<AssetBundle, JsValue> {
+    let library = load(bundle).map_err(to_js)?;
+
+    Ok(AssetBundle { library })
+}
+
+/// Validates a Character against the Assets in `bundle`, returning a JSON list
+/// of every rule it breaks, which is empty if it is valid
+#[wasm_bindgen]
+pub fn validate(bundle: &AssetBundle, source: &str) -> Result<String, JsValue> {
+    let errors = check(&bundle.library, source).map_err(to_js)?;
+
+    to_json(&errors)
+}
+
+/// Builds the Character Sheet of a Character from the Assets in `bundle`,
+/// returning it as JSON.
+///
+/// The Character is not validated, see [`validate`].
+#[wasm_bindgen(js_name = buildSheet)]
+pub fn build_sheet(bundle: &AssetBundle, source: &str) -> Result<String, JsValue> {
+    let character = parse(source).map_err(to_js)?;
+
+    to_json(&CharacterSheet::build(&character, &bundle.library))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const CHARACTER: &str = r#"{
+        "type": "character",
+        "metadata": { "id": "uuid-lmao-lol", "name": "foobar" },
+        "spec": {
+            "abilities": {
+                "strength": 16,
+                "dexterity": 12,
+                "constitution": 14,
+                "intelligence": 8,
+                "wisdom": 10,
+                "charisma": 13
+            },
+            "experience": 900,
+            "class": { "id": "fighter", "level": 3, "grants": [] },
+            "race": { "id": "human", "grants": [] }
+        }
+    }"#;
+
+    const BUNDLE: &str = r#"[
+        {
+            "type": "class",
+            "metadata": { "id": "fighter", "name": "Fighter" },
+            "spec": {
+                "set": { "hit-dice": "d10", "subclass-level": 3 },
+                "grant": [],
+                "select": []
+            }
+        },
+        {
+            "type": "language",
+            "metadata": { "id": "common", "name": "Common" }
+        }
+    ]"#;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("foobar", parse(CHARACTER).unwrap().metadata.name);
+        assert!(matches!(
+            parse("type: language\nmetadata: {id: common, name: Common}"),
+            Err(ValidatorError::Parse { .. })
+        ));
+        assert!(parse("{").is_err());
+    }
+
+    #[test]
+    fn test_load() {
+        assert_eq!(2, load(BUNDLE).unwrap().len());
+        assert_eq!(
+            2,
+            load("type: language\nmetadata: {id: common, name: Common}\n---\ntype: size\nmetadata: {id: medium, name: Medium}")
+                .unwrap()
+                .len()
+        );
+        assert!(load("[{\"type\": \"nope\"}]").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let library = load(BUNDLE).unwrap();
+
+        assert_eq!(
+            vec![json!({
+                "error": "missing-sub-class",
+                "class": "fighter",
+                "message": "the class `fighter` has no subclass",
+            })],
+            check(&library, CHARACTER).unwrap()
+        );
+    }
+}
@@ -0,0 +1,74 @@
+//! Tests of the bindings in a WebAssembly runtime, run with
+//! `wasm-pack test --node rust-src/wasm`.
+#![cfg(target_arch = "wasm32")]
+use wasm::{build_sheet, load_asset_bundle, parse_character, validate};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const CHARACTER: &str = "
+type: character
+metadata:
+  id: uuid-lmao-lol
+  name: foobar
+spec:
+  abilities:
+    strength: 16
+    dexterity: 12
+    constitution: 14
+    intelligence: 8
+    wisdom: 10
+    charisma: 13
+  class:
+    id: fighter
+    level: 1
+    grants: []
+  race:
+    id: human
+    grants: []
+";
+
+const BUNDLE: &str = "
+type: class
+metadata:
+  id: fighter
+  name: Fighter
+spec:
+  set:
+    hit-dice: d10
+  grant: []
+  select: []
+";
+
+/// Returns the field `key` of the object `value`
+fn field(value: &JsValue, key: &str) -> JsValue {
+    js_sys::Reflect::get(value, &JsValue::from_str(key)).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_parse_character() {
+    let json = parse_character(CHARACTER).unwrap();
+    assert!(json.contains("\"name\":\"foobar\""));
+
+    let error = parse_character("type: character").unwrap_err();
+    assert_eq!(
+        Some(String::from("parse")),
+        field(&error, "error").as_string()
+    );
+    assert!(field(&error, "message").as_string().is_some());
+}
+
+#[wasm_bindgen_test]
+fn test_validate() {
+    let bundle = load_asset_bundle(BUNDLE).unwrap();
+    assert_eq!(1, bundle.len());
+
+    assert_eq!("[]", validate(&bundle, CHARACTER).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn test_build_sheet() {
+    let bundle = load_asset_bundle(BUNDLE).unwrap();
+    let json = build_sheet(&bundle, CHARACTER).unwrap();
+
+    assert!(json.contains("\"hit_points\":12"));
+}
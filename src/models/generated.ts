@@ -0,0 +1,971 @@
+// Generated from the Rust models by the `typescript` crate, do not edit.
+// Regenerate with `npm run types`.
+
+/** A Character's Ability Scores */
+export type Abilities = {
+  /** Charisma */
+  charisma: number;
+  /** Constitution */
+  constitution: number;
+  /** Dexterity */
+  dexterity: number;
+  /** Intelligence */
+  intelligence: number;
+  /** Strength */
+  strength: number;
+  /** Wisdom */
+  wisdom: number;
+};
+
+/** One of the six Abilities */
+export type Ability =
+  | "strength"
+  | "dexterity"
+  | "constitution"
+  | "intelligence"
+  | "wisdom"
+  | "charisma";
+
+/** How a Character's base Ability Scores were generated */
+export type AbilityGeneration =
+  | {
+      method: "point-buy";
+    }
+  | {
+      method: "standard-array";
+    }
+  | {
+      method: "rolled";
+      /** The four dice rolled for each Ability */
+      rolls: Record<string, Array<number>>;
+    }
+  | {
+      method: "manual";
+    };
+
+/** Minimum Ability Scores */
+export type AbilityRequirement = Record<string, number> | Array<Record<string, number>>;
+
+/** What an [`AbilityRoll`] serializes as */
+export type AbilityRoll = {
+  /** The base score */
+  base: number;
+  /** Modifier of the score */
+  modifier: number;
+  /** What is added to the base score */
+  mods: number;
+  /** The score */
+  total: number;
+};
+
+/** The six Ability Scores of a Character */
+export type AbilityScores = {
+  charisma: AbilityRoll;
+  constitution: AbilityRoll;
+  dexterity: AbilityRoll;
+  intelligence: AbilityRoll;
+  strength: AbilityRoll;
+  wisdom: AbilityRoll;
+};
+
+/** Light, Medium or Heavy */
+export type ArmorCategory = "light" | "medium" | "heavy";
+
+/** Asset */
+export type Asset =
+  | {
+      /** The Character's metadata */
+      metadata: MetaData;
+      /** The Character's spec */
+      spec: CharacterSpec;
+      type: "character";
+    }
+  | {
+      /** MetaData */
+      metadata: MetaData;
+      /** Spec */
+      spec: Spec;
+      type: "class";
+    }
+  | {
+      /** MetaData */
+      metadata: MetaData;
+      type: "proficiency";
+    }
+  | {
+      /** MetaData */
+      metadata: MetaData;
+      type: "language";
+    }
+  | {
+      /** MetaData */
+      metadata: MetaData;
+      type: "size";
+    }
+  | {
+      /** MetaData */
+      metadata: MetaData;
+      type: "vision";
+    }
+  | {
+      /** The Feat's metadata */
+      metadata: MetaData;
+      /** The Feat's spec */
+      spec?: FeatSpec;
+      type: "feat";
+    }
+  | {
+      /** The Race's metadata */
+      metadata: MetaData;
+      /** The Race's spec */
+      spec?: RaceSpec;
+      type: "race";
+    }
+  | {
+      /** The SubRace's metadata */
+      metadata: MetaData;
+      /** The SubRace's spec */
+      spec: SubRaceSpec;
+      type: "sub-race";
+    }
+  | {
+      /** The Trait's metadata */
+      metadata: MetaData;
+      /** The Trait's spec */
+      spec?: TraitSpec;
+      type: "trait";
+    }
+  | {
+      /** The Feature's metadata */
+      metadata: MetaData;
+      /** The Feature's spec */
+      spec?: FeatureSpec;
+      type: "feature";
+    }
+  | {
+      /** The Background's metadata */
+      metadata: MetaData;
+      /** The Background's spec */
+      spec?: BackgroundSpec;
+      type: "background";
+    }
+  | {
+      /** The SubClass's metadata */
+      metadata: MetaData;
+      /** The SubClass's spec */
+      spec: SubClassSpec;
+      type: "sub-class";
+    }
+  | {
+      /** The Item's metadata */
+      metadata: MetaData;
+      /** The Item's spec */
+      spec: ItemSpec;
+      type: "item";
+    };
+
+/** An attack with an equipped weapon */
+export type Attack = {
+  /** Bonus to the attack roll */
+  bonus: number;
+  /** Damage dealt on a hit */
+  damage: DiceExpression;
+  /** Type of the damage dealt */
+  damage_type: string;
+  /** Unique ID of the weapon */
+  id: string;
+  /** Name of the weapon */
+  name: string;
+  /** Normal and long range, for ranged and thrown weapons */
+  range?: WeaponRange | null;
+  /** Whether the weapon needs two hands */
+  two_handed: boolean;
+  /** Damage dealt when wielded with two hands, for versatile weapons */
+  two_handed_damage?: DiceExpression | null;
+};
+
+/** Background Specification */
+export type BackgroundSpec = {
+  /** Starting equipment */
+  equipment?: Array<Equipment>;
+  /** Unique ID of the Feature the Background gives */
+  feature?: string | null;
+  /** Grants for Background Spec */
+  grant?: Array<Conditional_for_Grant>;
+  /** Selects for Background Spec */
+  select?: Array<Select>;
+  /** Skills the Background gives Proficiency in */
+  skills?: Array<Skill>;
+  /** Unique IDs of the tools the Background gives Proficiency with */
+  tools?: Array<string>;
+};
+
+/** An Item the Character is carrying */
+export type CarriedItem = {
+  /** Whether the Character is attuned to the Item */
+  attuned: boolean;
+  /** What kind of Item it is */
+  category: ItemCategory;
+  /** Whether the Item is worn or wielded */
+  equipped: boolean;
+  /** Unique ID of the Item */
+  id: string;
+  /** Name of the Item */
+  name: string;
+  /** How many of the Item */
+  quantity: number;
+  /** Weight of one of the Item, in pounds */
+  weight: number;
+};
+
+/** How much a Character carries, and how much they can */
+export type Carrying = {
+  capacity: number;
+  encumbrance: EncumbranceLevel;
+  push_drag_lift: number;
+  weight: number;
+};
+
+/** The Character Spec that stores all necessary data to build a Character Sheet */
+export type Character = {
+  /** The Character's metadata */
+  metadata: MetaData;
+  /** The Character's spec */
+  spec: CharacterSpec;
+};
+
+/** Assets */
+export type CharacterAssetGrant =
+  | {
+      /** Unique ID */
+      id: string;
+      type: "proficiency";
+    }
+  | {
+      /** Unique ID */
+      id: string;
+      type: "language";
+    }
+  | {
+      /** Unique ID */
+      id: string;
+      /** Unique ID of the option chosen, for Features that have options */
+      option?: string | null;
+      type: "feature";
+    }
+  | {
+      /** Unique ID */
+      id: string;
+      type: "spell";
+    }
+  | {
+      /** Unique ID */
+      id: string;
+      type: "size";
+    }
+  | {
+      /** Unique ID */
+      id: string;
+      /** How far the sense reaches */
+      range?: Distance | null;
+      type: "vision";
+    }
+  | {
+      /** The kind of movement */
+      id: Movement;
+      type: "speed";
+    } & (
+      | {
+          set: Distance;
+        }
+      | {
+          bonus: Distance;
+        }
+      | {
+          "equal-to": Movement;
+        })
+  | {
+      /** Assets that are granted to the Character by this Trait */
+      grants: Array<CharacterAssetGrant>;
+      /** Unique ID */
+      id: string;
+      type: "trait";
+    }
+  | {
+      /** Assets that are granted to the Character by this SubRace */
+      grants: Array<CharacterAssetGrant>;
+      /** Unique ID */
+      id: string;
+      type: "sub-race";
+    }
+  | {
+      /** Assets that are granted to the Character by this SubClass */
+      grants: Array<CharacterAssetGrant>;
+      /** Unique ID */
+      id: string;
+      type: "sub-class";
+    }
+  | {
+      type: "asi";
+    } & (
+      | {
+          ability: [Ability, Ability | null];
+        }
+      | {
+          feat: {
+            /** Ability increased by the Feat, for Feats that increase one */
+            ability?: Ability | null;
+            /** Assets chosen for the Feat's selects */
+            grants?: Array<CharacterAssetGrant>;
+            /** Feat ID */
+            id: string;
+          };
+        })
+  | {
+      /** Unique ID of the roll to grant advantage to */
+      id: string;
+      type: "advantage";
+    }
+  | {
+      /** Unique ID of the roll to grant disadvantage to */
+      id: string;
+      type: "disadvantage";
+    }
+  | {
+      /** Number to add to the AbilityScore */
+      add: number;
+      /** ID of the AbilityScore to modify */
+      id: Ability;
+      type: "abilityscore";
+    }
+  | {
+      /** Path of the stat */
+      id: string;
+      type: "stat";
+      /** Value to add to the stat */
+      value: Stat;
+    }
+  | {
+      /** ID of the AbilityScore to raise the maximum of */
+      id: Ability;
+      /** The new maximum */
+      max: number;
+      type: "ability-max";
+    };
+
+/** A Character's Background */
+export type CharacterBackground = {
+  /** Assets that are granted to the Character by this background */
+  grants: Array<CharacterAssetGrant>;
+  /** Unique ID */
+  id: string;
+};
+
+/** A Character's Class */
+export type CharacterClass = {
+  /** Assets that are granted to the Character by this class */
+  grants: Array<CharacterAssetGrant>;
+  /** Unique ID */
+  id: string;
+  /** The Class Level */
+  level: number;
+  /** If this class was chosen as a multi-class */
+  multiclass?: boolean;
+};
+
+/** CharacterClassSpec */
+export type CharacterClassSpec = Array<CharacterClass> | CharacterClass;
+
+/** Table options that change how a Character is built and validated */
+export type CharacterOptions = {
+  /** How carried weight slows the Character down */
+  encumbrance?: Encumbrance;
+  /** How the Character gains levels */
+  leveling?: Leveling;
+};
+
+/** A Character's Race */
+export type CharacterRace = {
+  /** Assets that are granted to the Character by this race */
+  grants: Array<CharacterAssetGrant>;
+  /** Unique ID */
+  id: string;
+};
+
+/**
+ * This Struct is directly used when filling out fields in the ui character sheet.
+ *
+ * It serializes with everything computed from the Character, such as the total and modifier of each Ability Score, so the ui doesn't need the rules.
+ */
+export type CharacterSheet = {
+  /** Ability Scores */
+  ability_scores: AbilityScores;
+  /** Armor Class */
+  armor_class: number;
+  /** Attacks with equipped weapons */
+  attacks: Array<Attack>;
+  /** What is carried, and what can be */
+  carrying: Carrying;
+  /** Experience points */
+  experience: number;
+  /** Experience points needed for the next level, if there is one */
+  experience_to_next_level?: number | null;
+  /** Features and Traits, along with the options chosen for them */
+  features: Array<MetaData>;
+  /** Hit Dice of each class */
+  hit_dice: Array<HitDice>;
+  /** Maximum hit points */
+  hit_points: number;
+  /** Items carried */
+  items: Array<CarriedItem>;
+  /** Unique IDs of the languages known */
+  languages: Array<string>;
+  /** Total level, of every class */
+  level: number;
+  /** Name of the Character */
+  name: string;
+  /** Proficiencies */
+  proficiencies: Array<MetaData>;
+  /** Proficiency bonus */
+  proficiency_bonus: number;
+  /** Senses */
+  senses: Senses;
+  /** Unique ID of the size */
+  size: string;
+  /** Every Skill */
+  skills: Record<string, SkillCheck>;
+  /** Speeds */
+  speeds: Record<string, Distance>;
+  /** Unique IDs of the Spells known */
+  spells: Array<string>;
+  /** Stats added to by grants, by their Unique ID */
+  stats: Record<string, Stat>;
+};
+
+/** Character Specification */
+export type CharacterSpec = {
+  /** The Ability Scores for a Character */
+  abilities: Abilities;
+  /** CharacterBackground */
+  background?: CharacterBackground | null;
+  /** CharacterClassSpec */
+  class: CharacterClassSpec;
+  /** The Character's coins */
+  currency?: Currency;
+  /** The Character's experience points */
+  experience?: number;
+  /** How the base Ability Scores were generated */
+  generation?: AbilityGeneration;
+  /** Everything the Character is carrying */
+  inventory?: Inventory;
+  /** Table options that change how the Character is built */
+  options?: CharacterOptions;
+  /** CharacterRace */
+  race: CharacterRace;
+};
+
+/**
+ * A Condition that must hold for something to apply to a Character.
+ *
+ * Every field that is set must hold for the Condition to be met.
+ */
+export type Condition = {
+  /** A Condition that must not hold */
+  $not?: Condition | null;
+  /** Minimum Ability Scores, these are always minimum requirements */
+  ability?: AbilityRequirement | null;
+  /** Classes, at least one of which the Character must have */
+  class?: OneOrMany_for_String | null;
+  /** Levels of the class this rule is in, met once any of them is reached */
+  level?: OneOrMany_for_int8 | null;
+  /** Classes, at least one of which the Character must have multi-classed into */
+  multiclass?: OneOrMany_for_String | null;
+  /** Proficiencies, at least one of which the Character must have */
+  proficiency?: OneOrMany_for_String | null;
+  /** Races or SubRaces, one of which the Character must be */
+  race?: OneOrMany_for_String | null;
+  /** Whether the Character must be able to cast at least one spell */
+  spellcasting?: boolean | null;
+};
+
+/** A value that only applies to a Character when its Condition holds */
+export type Conditional_for_Grant = {
+  /** The Condition gating the value */
+  if?: Condition | null;
+} & (
+  | {
+      /** Unique ID */
+      id: string;
+      type: "proficiency";
+    }
+  | {
+      /** Unique ID */
+      id: string;
+      type: "feature";
+    }
+  | {
+      /** Unique ID */
+      id: string;
+      type: "language";
+    }
+  | {
+      /** Unique ID */
+      id: string;
+      type: "trait";
+    }
+  | {
+      /** Unique ID */
+      id: string;
+      type: "spell";
+    }
+  | {
+      /** Unique ID */
+      id: string;
+      type: "size";
+    }
+  | {
+      /** Unique ID */
+      id: string;
+      /** How far the sense reaches */
+      range?: Distance | null;
+      type: "vision";
+    }
+  | {
+      /** The kind of movement */
+      id: Movement;
+      type: "speed";
+    } & (
+      | {
+          set: Distance;
+        }
+      | {
+          bonus: Distance;
+        }
+      | {
+          "equal-to": Movement;
+        })
+  | {
+      /** Unique ID of the roll */
+      id: string;
+      type: "advantage";
+    }
+  | {
+      /** Unique ID of the roll */
+      id: string;
+      type: "disadvantage";
+    });
+
+/** What something costs */
+export type Cost = string;
+
+/** Coins a Character has */
+export type Currency = {
+  /** Copper Pieces */
+  copper?: number;
+  /** Electrum Pieces */
+  electrum?: number;
+  /** Gold Pieces */
+  gold?: number;
+  /** Platinum Pieces */
+  platinum?: number;
+  /** Silver Pieces */
+  silver?: number;
+};
+
+/** A dice expression */
+export type DiceExpression = string | number;
+
+/** Die, for anything that is a single die rather than a [`crate::dice::DiceExpression`] */
+export type Die = "d4" | "d6" | "d8" | "d10" | "d12" | "d20";
+
+/** A distance in feet */
+export type Distance = string | number;
+
+/** Which encumbrance rules a Character uses */
+export type Encumbrance = "standard" | "variant";
+
+/** How encumbered a Character is, with the variant encumbrance rules */
+export type EncumbranceLevel = "unencumbered" | "encumbered" | "heavily-encumbered";
+
+/** An item and how many of it */
+export type Equipment = {
+  /** Unique ID of the item */
+  id: string;
+  /** How many of the item */
+  quantity?: number;
+};
+
+/** Feat Specification */
+export type FeatSpec = {
+  /** Abilities, one of which the Character increases by 1 when taking this Feat */
+  ability?: OneOrMany_for_Ability | null;
+  /** Grants for Feat Spec */
+  grant?: Array<Conditional_for_Grant>;
+  /** What a Character must meet to take this Feat */
+  prerequisite?: Condition | null;
+  /** Selects for Feat Spec */
+  select?: Array<Select>;
+};
+
+/** What a Feature, or an option of one, does to a Character */
+export type FeatureEffects = {
+  /** Grants */
+  grant?: Array<Conditional_for_Grant>;
+  /** Stats, by their path on the sheet */
+  stat?: Record<string, Stat>;
+};
+
+/** An option of a [`Feature`] */
+export type FeatureOption = {
+  /** What a Character must meet to choose this option */
+  if?: Condition | null;
+  /** The option's metadata */
+  metadata: MetaData;
+  /** Grants and stats a Character that chose this option gets */
+  spec?: FeatureEffects;
+};
+
+/** Feature Specification */
+export type FeatureSpec = {
+  /** Grants */
+  grant?: Array<Conditional_for_Grant>;
+  /** Options, one of which must be chosen if there are any */
+  options?: Array<FeatureOption>;
+  /** Stats, by their path on the sheet */
+  stat?: Record<string, Stat>;
+};
+
+/** The Hit Dice a Character has from one of their classes */
+export type HitDice = {
+  /** Unique ID of the class */
+  class: string;
+  /** How many of the Hit Die, one for every level in the class */
+  count: number;
+  /** The Hit Die of the class */
+  die: Die;
+  /** Name of the class */
+  name: string;
+};
+
+/** Everything a Character is carrying */
+export type Inventory = {
+  /** Items carried, including containers and what is in them */
+  items?: Array<InventoryItem>;
+};
+
+/** An Item in an [`Inventory`] */
+export type InventoryItem = {
+  /** Whether the Character is attuned to the Item, which Items inside of containers can't be */
+  attuned?: boolean;
+  /** Items inside of this one, for containers */
+  contents?: Array<InventoryItem>;
+  /** Whether the Item is worn or wielded, which Items inside of containers can't be */
+  equipped?: boolean;
+  /** Unique ID of the Item */
+  id: string;
+  /** How many of the Item */
+  quantity?: number;
+};
+
+/** What kind of Item something is, without anything specific to the Item */
+export type ItemCategory = "weapon" | "armor" | "shield" | "container" | "gear";
+
+/** Item Specification */
+export type ItemSpec = {
+  /** Whether the Item must be attuned to for its magic to work */
+  attunement?: boolean;
+  /** What the Item costs */
+  cost?: Cost | null;
+  /** Weight, in pounds */
+  weight?: number;
+} & (
+  | {
+      /** Simple or Martial */
+      category: WeaponCategory;
+      /** Damage dealt on a hit, before any modifier */
+      damage: DiceExpression;
+      /** Type of the damage dealt */
+      "damage-type": string;
+      kind: "weapon";
+      /** Properties */
+      properties?: Array<WeaponProperty>;
+      /** Range, for ranged weapons */
+      range?: WeaponRange | null;
+    }
+  | {
+      /** Base Armor Class */
+      ac: number;
+      /** Light, Medium or Heavy */
+      category: ArmorCategory;
+      kind: "armor";
+      /** Whether it gives disadvantage on Stealth checks */
+      stealth?: boolean;
+      /** Strength needed to wear it without being slowed */
+      strength?: number | null;
+    }
+  | {
+      /** Bonus to Armor Class while wielded */
+      ac: number;
+      kind: "shield";
+    }
+  | {
+      /** Weight it can hold, in pounds */
+      capacity: number;
+      kind: "container";
+    }
+  | {
+      kind: "gear";
+    });
+
+/** How a Character gains levels */
+export type Leveling = "experience" | "milestone";
+
+/** MetaData */
+export type MetaData = {
+  /** A Description */
+  description?: string | null;
+  /** Unique Id for this Asset */
+  id: string;
+  /** Pretty Name to be displayed */
+  name: string;
+  /** User-written notes */
+  notes?: string | null;
+  [key: string]: unknown;
+};
+
+/** A way a Character can move, each of which has its own speed */
+export type Movement = "walk" | "fly" | "climb" | "swim" | "burrow";
+
+/** Either a single value or a list of values */
+export type OneOrMany_for_Ability = Ability | Array<Ability>;
+
+/** Either a single value or a list of values */
+export type OneOrMany_for_String = string | Array<string>;
+
+/** Either a single value or a list of values */
+export type OneOrMany_for_int8 = number | Array<number>;
+
+/** Race Specification */
+export type RaceSpec = {
+  /** Bonuses to Ability Scores */
+  ability?: Record<string, number>;
+  /** Grants for Race Spec */
+  grant?: Array<Conditional_for_Grant>;
+  /** Selects for Race Spec */
+  select?: Array<Select>;
+  /** Unique ID of the Size of the Race */
+  size?: string | null;
+  /** Speeds of the Race */
+  speed?: Record<string, Distance>;
+};
+
+/** Select */
+export type Select =
+  | {
+      /** The ids that may be selected */
+      id: Array<string>;
+      /** Pretty Name to be displayed */
+      name: string;
+      /** The number of ids that can be selected */
+      number: number;
+      type: "proficiency";
+    }
+  | {
+      /** The ids that may be selected */
+      id: Array<string>;
+      /** Pretty Name to be displayed */
+      name: string;
+      /** The number of ids that can be selected */
+      number: number;
+      type: "feature";
+    }
+  | {
+      /** The ids that may be selected */
+      id: Array<string>;
+      /** Pretty Name to be displayed */
+      name: string;
+      /** The number of ids that can be selected */
+      number: number;
+      type: "language";
+    }
+  | {
+      /** The ids that may be selected */
+      id: Array<string>;
+      /** Pretty Name to be displayed */
+      name: string;
+      /** The number of ids that can be selected */
+      number: number;
+      type: "trait";
+    }
+  | {
+      /** The ids that may be selected */
+      id: Array<string>;
+      /** Pretty Name to be displayed */
+      name: string;
+      /** The number of ids that can be selected */
+      number: number;
+      type: "spell";
+    }
+  | {
+      /** The ids that may be selected */
+      id: Array<string>;
+      /** Pretty Name to be displayed */
+      name: string;
+      /** The number of ids that can be selected */
+      number: number;
+      type: "size";
+    }
+  | {
+      /** The ids that may be selected */
+      id: Array<string>;
+      /** Pretty Name to be displayed */
+      name: string;
+      /** The number of ids that can be selected */
+      number: number;
+      type: "vision";
+    }
+  | {
+      /** The ids that may be selected */
+      id: Array<string>;
+      /** Pretty Name to be displayed */
+      name: string;
+      /** The number of ids that can be selected */
+      number: number;
+      type: "speed";
+    }
+  | {
+      /** The ids that may be selected */
+      id: Array<string>;
+      /** Pretty Name to be displayed */
+      name: string;
+      /** The number of ids that can be selected */
+      number: number;
+      type: "advantage";
+    }
+  | {
+      /** The ids that may be selected */
+      id: Array<string>;
+      /** Pretty Name to be displayed */
+      name: string;
+      /** The number of ids that can be selected */
+      number: number;
+      type: "disadvantage";
+    };
+
+/** The senses of a Character */
+export type Senses = {
+  passive_perception: number;
+  /** The best range of each sense, by its Unique ID */
+  ranges: Record<string, Distance | null>;
+};
+
+/** Setter */
+export type Setter = {
+      /** Class Setter Hit Dice */
+      "hit-dice": Die;
+      /** Class Setter level at which a SubClass is chosen */
+      "subclass-level"?: number | null;
+    };
+
+/** One of the Skills, each of which is checked with an [`Ability`] */
+export type Skill =
+  | "acrobatics"
+  | "animal-handling"
+  | "arcana"
+  | "athletics"
+  | "deception"
+  | "history"
+  | "insight"
+  | "intimidation"
+  | "investigation"
+  | "medicine"
+  | "nature"
+  | "perception"
+  | "performance"
+  | "persuasion"
+  | "religion"
+  | "sleight-of-hand"
+  | "stealth"
+  | "survival";
+
+/** Checks with a Skill */
+export type SkillCheck = {
+  /** Modifier of the check */
+  modifier: number;
+  /** Whether the Character is proficient in the Skill */
+  proficient: boolean;
+};
+
+/** Spec */
+export type Spec = {
+      /** Grants for Class Spec */
+      grant: Array<Conditional_for_Grant>;
+      /** Selects for Class Spec */
+      select: Array<Select>;
+      /** Setters for Class Spec */
+      set: Setter;
+    };
+
+/** A value that something adds to a stat on the Character sheet */
+export type Stat = boolean | number;
+
+/** SubClass Specification */
+export type SubClassSpec = {
+  /** Unique ID of the Class this is a SubClass of */
+  class: string;
+  /** Grants for SubClass Spec, with levels being levels of the Class */
+  grant?: Array<Conditional_for_Grant>;
+  /** Selects for SubClass Spec */
+  select?: Array<Select>;
+};
+
+/** SubRace Specification */
+export type SubRaceSpec = {
+  /** Bonuses to Ability Scores */
+  ability?: Record<string, number>;
+  /** Grants for Race Spec */
+  grant?: Array<Conditional_for_Grant>;
+  /** Unique ID of the Race this is a SubRace of */
+  race: string;
+  /** Selects for Race Spec */
+  select?: Array<Select>;
+  /** Unique ID of the Size of the Race */
+  size?: string | null;
+  /** Speeds of the Race */
+  speed?: Record<string, Distance>;
+};
+
+/** Trait Specification */
+export type TraitSpec = {
+  /** Grants for Trait Spec */
+  grant?: Array<Conditional_for_Grant>;
+  /** Selects for Trait Spec */
+  select?: Array<Select>;
+};
+
+/** Simple or Martial */
+export type WeaponCategory = "simple" | "martial";
+
+/** A Weapon Property */
+export type WeaponProperty =
+  | "ammunition"
+  | "finesse"
+  | "heavy"
+  | "light"
+  | "loading"
+  | "reach"
+  | {
+      thrown: WeaponRange;
+    }
+  | "two-handed"
+  | {
+      versatile: DiceExpression;
+    };
+
+/** Normal and long range of a ranged or thrown weapon */
+export type WeaponRange = {
+  /** Furthest range, attacking with disadvantage beyond normal range */
+  long: Distance;
+  /** Range without disadvantage */
+  normal: Distance;
+};